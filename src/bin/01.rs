@@ -1,71 +1,38 @@
 advent_of_code::solution!(1);
-use std::char;
+use advent_of_code::utils::parsers::PatternMatcher;
 
-const DIGITS: [&str; 9] = [
+const DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+/// Spelled-out digits, the puzzle only counts "one" to "nine".
+const WORDS: [&str; 9] = [
     "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
 ];
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut result = 0;
-    input.lines().for_each(|l| {
-        if let Some(value) = find_first_and_last(l, &None, &None) {
-            result += value;
-        }
-    });
-    Some(result)
+/// Maps the patterns to consecutive values, starting at `first`.
+fn vocabulary(
+    patterns: &'static [&'static str],
+    first: u32,
+) -> impl Iterator<Item = (&'static str, u32)> {
+    patterns.iter().zip(first..).map(|(p, digit)| (*p, digit))
 }
 
-fn find_first_and_last(
-    input: &str,
-    digits: &Option<Vec<String>>,
-    reversed_digits: &Option<Vec<String>>,
-) -> Option<u32> {
-    let first = find_digit(input, &digits)?;
-
-    let reversed_input: String = input.chars().rev().collect();
-    let last = find_digit(&reversed_input, &reversed_digits)?;
-
-    let mut result = String::from(first);
-    result.push(last);
-    result.parse().ok()
+/// Sums the first and last digit of every line, lines without digits count as zero.
+fn sum_calibration_values(input: &str, matcher: &PatternMatcher<u32>) -> Option<u32> {
+    Some(
+        input
+            .lines()
+            .filter_map(|l| matcher.find_first_and_last(l))
+            .map(|(first, last)| first.value * 10 + last.value)
+            .sum(),
+    )
 }
 
-fn find_digit(input: &str, digits: &Option<Vec<String>>) -> Option<char> {
-    let mut smallest_index = match input.find(|c: char| c.is_numeric()) {
-        Some(index) => index,
-        None => input.len(),
-    };
-
-    let mut counter: u32 = 0;
-    let mut result = input.chars().nth(smallest_index);
-
-    if digits.is_some() {
-        for digit in digits.as_ref().unwrap() {
-            counter += 1;
-            let index = match input.find(digit) {
-                Some(val) => val,
-                None => continue,
-            };
-            if index < smallest_index {
-                smallest_index = index;
-                result = char::from_digit(counter, 10);
-            }
-        }
-    }
-    result
+pub fn part_one(input: &str) -> Option<u32> {
+    sum_calibration_values(input, &PatternMatcher::new(vocabulary(&DIGITS, 0)))
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let mut result = 0;
-    let digits = Some(DIGITS.iter().map(|&s| s.to_string()).collect());
-    let reversed_digits = Some(DIGITS.iter().map(|&d| d.chars().rev().collect()).collect());
-
-    input.lines().for_each(|l| {
-        if let Some(value) = find_first_and_last(l, &digits, &reversed_digits) {
-            result += value
-        };
-    });
-    Some(result)
+    let matcher = PatternMatcher::new(vocabulary(&DIGITS, 0).chain(vocabulary(&WORDS, 1)));
+    sum_calibration_values(input, &matcher)
 }
 
 #[cfg(test)]
@@ -81,9 +48,24 @@ mod tests {
         );
         assert_eq!(result, Some(142));
     }
+
+    #[test]
+    fn test_overlapping_words() {
+        let result = part_two("eightwo\nxtwone3\noneight");
+        assert_eq!(result, Some(82 + 23 + 18));
+    }
+
+    #[test]
+    fn test_zero() {
+        assert_eq!(part_one("a0b7\n30"), Some(7 + 30));
+        assert_eq!(part_two("zero0nine"), Some(9));
+    }
+
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file_part(
+            "examples", DAY, 1,
+        ));
         assert_eq!(result, Some(142));
     }
 
@@ -93,8 +75,3 @@ mod tests {
         assert_eq!(result, Some(281));
     }
 }
-
-// First solution
-// pt1: 16.7ms & pt2: 17.5ms
-// Second solution: move creation of Vec<String> for pt2 out of loop.
-// pt1: 4.8ms & pt2: 7.8ms
@@ -77,7 +77,3 @@ mod tests {
         assert_eq!(result, Some(2286));
     }
 }
-
-// First try: 2.5ms
-// Second try: pt1: 3.7ms pt2: 7.7ms
-// Third try: pt1: 2.4ms pt2: 2.3ms
@@ -180,6 +180,3 @@ mod tests {
         assert_eq!(result, Some(467835));
     }
 }
-
-// First try: pt1: 3.4s
-// Second try: pt1: 28.2 ms & pt2: 12.5ms
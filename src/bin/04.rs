@@ -92,6 +92,3 @@ mod tests {
         assert_eq!(result, Some(30));
     }
 }
-
-// pt1 10ms
-// pt1 5.2ms & pt2 5.6ms
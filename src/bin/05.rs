@@ -104,6 +104,140 @@ pub fn part_two(input: &str) -> Option<u32> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::utils::testing::*;
+
+    /// Seed ranges and maps with small numbers, so the brute-force reference stays fast.
+    struct Almanac;
+
+    #[derive(Clone, Debug)]
+    struct AlmanacValue {
+        seeds: Vec<(i64, i64)>,
+        maps: Vec<Vec<(i64, i64, i64)>>,
+    }
+
+    impl AlmanacValue {
+        fn to_input(&self) -> String {
+            let seeds: Vec<String> = self
+                .seeds
+                .iter()
+                .map(|(start, range)| format!("{start} {range}"))
+                .collect();
+            let mut input = format!("seeds: {}\n", seeds.join(" "));
+            for (i, map) in self.maps.iter().enumerate() {
+                input.push_str(&format!("\nmap-{i} map:\n"));
+                for (destination, source, range) in map {
+                    input.push_str(&format!("{destination} {source} {range}\n"));
+                }
+            }
+            input
+        }
+    }
+
+    impl Generator for Almanac {
+        type Value = AlmanacValue;
+
+        fn generate(&self, rng: &mut Rng) -> AlmanacValue {
+            let seeds = (0..rng.range(1, 3))
+                .map(|_| (rng.range(0, 100), rng.range(1, 20)))
+                .collect();
+            let maps = (0..rng.range(1, 4))
+                .map(|_| {
+                    (0..rng.range(0, 4))
+                        .map(|_| (rng.range(0, 100), rng.range(0, 100), rng.range(1, 30)))
+                        .collect()
+                })
+                .collect();
+            AlmanacValue { seeds, maps }
+        }
+
+        fn shrink(&self, value: &AlmanacValue) -> Vec<AlmanacValue> {
+            let mut candidates = vec![];
+            for seeds in shrink_vec(&value.seeds)
+                .into_iter()
+                .filter(|s| !s.is_empty())
+            {
+                candidates.push(AlmanacValue {
+                    seeds,
+                    ..value.clone()
+                });
+            }
+            for maps in shrink_vec(&value.maps) {
+                candidates.push(AlmanacValue {
+                    maps,
+                    ..value.clone()
+                });
+            }
+            for (i, map) in value.maps.iter().enumerate() {
+                for smaller in shrink_vec(map) {
+                    let mut candidate = value.clone();
+                    candidate.maps[i] = smaller;
+                    candidates.push(candidate);
+                }
+            }
+            candidates
+        }
+    }
+
+    /// Candidate range-based solver for part two, checked against the brute-force seed loop.
+    /// A range is split wherever it partially overlaps a map line, the first matching line wins, just like in `transform`.
+    fn calculate_closest_location_from_ranges(ranges: Vec<i64>, maps: &SeedMap) -> Option<u32> {
+        let mut current: Vec<(i64, i64)> = ranges
+            .chunks_exact(2)
+            .filter(|pair| pair[1] > 0)
+            .map(|pair| (pair[0], pair[0] + pair[1]))
+            .collect();
+
+        for map in maps {
+            let mut mapped = vec![];
+
+            for line in map {
+                let source_end = line.source + line.range;
+                let offset = line.destination - line.source;
+                let mut unmapped = vec![];
+
+                for (start, end) in current {
+                    let overlap_start = start.max(line.source);
+                    let overlap_end = end.min(source_end);
+
+                    if overlap_start >= overlap_end {
+                        unmapped.push((start, end));
+                        continue;
+                    }
+
+                    mapped.push((overlap_start + offset, overlap_end + offset));
+                    if start < overlap_start {
+                        unmapped.push((start, overlap_start));
+                    }
+                    if overlap_end < end {
+                        unmapped.push((overlap_end, end));
+                    }
+                }
+
+                current = unmapped;
+            }
+
+            current.extend(mapped);
+        }
+
+        current.iter().map(|(start, _)| *start as u32).min()
+    }
+
+    #[test]
+    fn test_ranges_match_brute_force() {
+        differential(
+            &Almanac,
+            |almanac| {
+                let input = almanac.to_input();
+                let (seeds, maps) = parse_input(&input);
+                calculate_seeds_from_ranges(seeds.collect(), maps)
+            },
+            |almanac| {
+                let input = almanac.to_input();
+                let (seeds, maps) = parse_input(&input);
+                calculate_closest_location_from_ranges(seeds.collect(), &maps).unwrap()
+            },
+        );
+    }
 
     #[test]
     fn test_part_one() {
@@ -117,7 +251,3 @@ mod tests {
         assert_eq!(result, Some(46));
     }
 }
-
-// #1: 454.1µs
-// #2  pt1: 354µs & pt2: 3618.3s
-// #3: Use data parallelisation with Rayon: pt1 9.4 ms, pt2: 701s
@@ -1,52 +1,38 @@
 advent_of_code::solution!(6);
 
-use std::iter::Zip;
-
+use advent_of_code::utils::math::count_above_inverted_parabola;
 use advent_of_code::utils::parsers::*;
 
-fn parse_input(input: &str) -> Zip<impl Iterator<Item = u64> + '_, impl Iterator<Item = u64> + '_> {
+fn parse_input(input: &str) -> (&str, &str) {
     let mut lines = input.lines();
-    let time = parse_line::<u64>(lines.next().unwrap());
-    let distances = parse_line::<u64>(lines.next().unwrap());
-    time.zip(distances)
-}
-
-fn calculate_distances(time: &u64) -> Vec<u64> {
-    let mut possible_distances = vec![];
-    for i in 1..*time {
-        let speed = i;
-        possible_distances.push((time - i) * speed);
-    }
-    possible_distances
+    (lines.next().unwrap(), lines.next().unwrap())
 }
 
-fn ways_to_win(distances: Vec<u64>, record: &u64) -> usize {
-    distances.iter().filter(|&dist| dist > record).count()
+/// Reads all digits of a line as one number, ignoring the spaces between them.
+fn parse_kerned(line: &str) -> u64 {
+    line.bytes()
+        .filter(u8::is_ascii_digit)
+        .fold(0, |acc, digit| acc * 10 + u64::from(digit - b'0'))
 }
 
-fn calculate_result(input: Vec<(u64, u64)>) -> u64 {
-    let mut result = 1;
-    input.iter().for_each(|(time, record)| {
-        let distances = calculate_distances(time);
-        result *= ways_to_win(distances, record);
-    });
-    result as u64
+/// Hold times `h` beat the record if the distance `h * (time - h)` exceeds it.
+fn ways_to_win(time: u64, record: u64) -> u64 {
+    count_above_inverted_parabola(time, record)
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let input = parse_input(input).collect();
-    Some(calculate_result(input))
-}
-
-fn concat_f(a: u64, b: u64) -> u64 {
-    format!("{a}{b}").parse().unwrap()
+    let (times, records) = parse_input(input);
+    let races = parse_line::<u64>(times).zip(parse_line::<u64>(records));
+    Some(
+        races
+            .map(|(time, record)| ways_to_win(time, record))
+            .product(),
+    )
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let input = parse_input(input);
-    let input =
-        input.reduce(|(acc_d, acc_t), (e_d, e_t)| (concat_f(acc_d, e_d), concat_f(acc_t, e_t)));
-    Some(calculate_result(vec![input.unwrap()]))
+    let (time, record) = parse_input(input);
+    Some(ways_to_win(parse_kerned(time), parse_kerned(record)))
 }
 
 #[cfg(test)]
@@ -65,6 +51,3 @@ mod tests {
         assert_eq!(result, Some(71503));
     }
 }
-
-// #1: (111.6µs)
-// #2; Part 1: 32076 (80.0µs) Part 2: 34278221 (3.0s)
@@ -111,7 +111,9 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file_part(
+            "examples", DAY, 1,
+        ));
         assert_eq!(result, Some(2));
     }
 
@@ -126,6 +128,3 @@ mod tests {
     //     part_two(&advent_of_code::template::read_file("examples", DAY));
     // }
 }
-
-// #1 (24.4ms)
-// #2 Part 1: 14681 (16.0ms) Part 2: 14321394058031 (103.1ms)
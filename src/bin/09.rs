@@ -95,5 +95,3 @@ mod tests {
         assert_eq!(result, Some(2));
     }
 }
-
-// #1 Part 1: 2098530125 (6.8ms) && Part 2: 1016 (6.7ms)
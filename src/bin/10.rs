@@ -1,195 +1,20 @@
 advent_of_code::solution!(10);
-use advent_of_code::utils::map::*;
+use advent_of_code::utils::pipes::*;
 
-#[derive(Clone, Debug)]
-struct Tile {
-    position: Position,
-    tile_type: char,
-}
-
-type TileMap<'a> = Vec<&'a str>;
-
-fn create_map<'a>(input: &'a str) -> TileMap<'a> {
-    let mut map: TileMap = vec![];
-    input.lines().for_each(|l| map.push(l));
-    map
-}
-
-fn get_starting_position(map: &TileMap) -> Position {
-    for i in 0..map.len() {
-        if let Some(x) = map[i].find(|c| c == 'S') {
-            return (x, i);
-        };
-    }
-    panic!("No starting position found!");
-}
-
-fn get_tile_type(position: Position, map: &TileMap) -> u8 {
-    let (x, y) = position;
-    map[y].as_bytes()[x]
-}
-
-fn get_starting_char(position: &Position, map: &TileMap) -> char {
-    let (x, y) = *position;
-    if x > 0 {
-        let left = get_tile_type((x - 1, y), map);
-        match left {
-            b'-' | b'F' | b'L' => {
-                if let Some(_type) = get_if_j_or_7(&position, &map) {
-                    return _type;
-                }
-                if x + 1 < map[0].len() {
-                    let right = get_tile_type((x + 1, y), &map);
-                    if right == b'-' || right == b'7' || right == b'J' {
-                        return '-';
-                    }
-                }
-                panic!("Invalid input!");
-            }
-            _ => {
-                let below = get_tile_type((x, y + 1), &map);
-                match below {
-                    b'L' | b'|' | b'J' => {
-                        if x + 1 < map[0].len() {
-                            let right = get_tile_type((x + 1, y), &map);
-                            if right == b'-' || right == b'7' || right == b'J' {
-                                return 'F';
-                            }
-                        }
-                        if y > 0 {
-                            let above = get_tile_type((x, y - 1), &map);
-                            if above == b'7' || above == b'F' || above == b'|' {
-                                return '7';
-                            }
-                        }
-                        panic!("Invalid input!");
-                    }
-                    _ => {
-                        if y > 0 {
-                            let above = get_tile_type((x, y - 1), &map);
-                            if above == b'7' || above == b'F' || above == b'|' {
-                                'L';
-                            }
-                        }
-                        panic!("Invalid Input!");
-                    }
-                };
-            }
-        };
-    } else {
-        let right = get_tile_type((x + 1, y), map);
-        match right {
-            b'7' | b'-' | b'J' => return get_if_j_or_7(&position, &map).unwrap(),
-            _ => panic!("Invalid input"),
-        }
-    }
-}
-
-fn get_if_j_or_7(position: &Position, map: &TileMap) -> Option<char> {
-    let (x, y) = *position;
-    if y > 0 {
-        let above = get_tile_type((x, y - 1), &map);
-        if above == b'F' || above == b'7' || above == b'|' {
-            return Some('J');
-        }
-    }
-    if y + 1 < map.len() {
-        let below = get_tile_type((x, y + 1), &map);
-        if below == b'J' || below == b'L' || below == b'|' {
-            return Some('7');
-        }
-    }
-    None
-}
-
-fn next_position(previous_pos: &Position, current: &Tile) -> Position {
-    let current_position = &current.position;
-    let res = match current.tile_type {
-        '|' => Map::move_vertical(current_position, previous_pos),
-        '-' => Map::move_horizontal(current_position, previous_pos),
-        'L' => Map::move_north_east(current_position, previous_pos),
-        'J' => Map::move_north_west(current_position, previous_pos),
-        '7' => Map::move_south_west(current_position, previous_pos),
-        'F' => Map::move_south_east(current_position, previous_pos),
-        _ => panic!("Impossible type :{:?}", current.tile_type),
-    };
-    res
-}
-
-fn find_loop(map: &TileMap) -> Vec<Position> {
-    let starting_position = get_starting_position(map);
-    let mut loop_positions: Vec<Position> = vec![starting_position];
-    let mut previous_position = starting_position;
-    let mut current_tile = Tile {
-        position: starting_position,
-        tile_type: get_starting_char(&starting_position, map),
-    };
-    loop {
-        let next_position = next_position(&previous_position, &current_tile);
-        if next_position == starting_position {
-            return loop_positions;
-        }
-        previous_position = current_tile.position;
-        let tile_type = get_tile_type(next_position, map) as char;
-        current_tile = Tile {
-            position: next_position,
-            tile_type,
-        };
-        loop_positions.push(current_tile.position.clone());
-    }
+fn parse_input(input: &str) -> Option<(PipeGrid, Bitset)> {
+    let grid = PipeGrid::parse(input).map_err(|e| eprintln!("{e}")).ok()?;
+    let cells = grid.find_loop().map_err(|e| eprintln!("{e}")).ok()?;
+    Some((grid, cells))
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let map = create_map(input);
-    let resulting_loop = find_loop(&map);
-    Some((resulting_loop.len() / 2) as u32)
+    let (_, cells) = parse_input(input)?;
+    Some((cells.len() / 2) as u32)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let map = create_map(input);
-    let width = map[0].len();
-    let closed_loop = find_loop(&map);
-    let mut count = 0;
-    let mut is_inside = false;
-    for y in 0..map.len() {
-        let mut half_cross = b'.';
-        for x in 0..width {
-            let current_position = (x, y);
-            let mut tile_type = get_tile_type(current_position, &map);
-            if tile_type == b'S' {
-                tile_type = get_starting_char(&current_position, &map) as u8;
-            }
-
-            if (tile_type == b'L' || tile_type == b'F') && closed_loop.contains(&current_position) {
-                half_cross = tile_type;
-            }
-
-            if (half_cross == b'L' && tile_type == b'J')
-                || (half_cross == b'F' && tile_type == b'7')
-                    && closed_loop.contains(&current_position)
-            {
-                half_cross = tile_type;
-            }
-
-            if (half_cross == b'L' && tile_type == b'7')
-                || (half_cross == b'F' && tile_type == b'J')
-                    && closed_loop.contains(&current_position)
-            {
-                is_inside = !is_inside;
-                half_cross = tile_type;
-            }
-
-            if (tile_type == b'|') && closed_loop.contains(&current_position) {
-                is_inside = !is_inside;
-            }
-
-            if is_inside && !closed_loop.contains(&current_position) {
-                count += 1;
-            }
-        }
-    }
-
-    Some(count)
+    let (grid, cells) = parse_input(input)?;
+    Some(grid.count_enclosed(&cells) as u32)
 }
 
 #[cfg(test)]
@@ -209,6 +34,17 @@ mod tests {
         assert_eq!(result, Some(4));
     }
 
+    #[test]
+    fn test_stray_pipe_at_start() {
+        let maze = ".|...
+.S-7.
+.|.|.
+.L-J.
+.....";
+        assert_eq!(part_one(maze), Some(4));
+        assert_eq!(part_two(maze), Some(1));
+    }
+
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
@@ -266,6 +102,3 @@ L7JLJL-JLJLJL--JLJ.L",
         assert_eq!(result, Some(10));
     }
 }
-
-// #1 Part 1: 6768 (1.9ms)
-// #2 Part 1: 6768 (2.5ms) Part 2: 351 (4.0s)
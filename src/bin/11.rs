@@ -1,78 +1,65 @@
 advent_of_code::solution!(11);
-use rayon::*;
-use std::{collections::HashSet, str::Lines, vec::IntoIter};
 
 use advent_of_code::utils::map::*;
-use itertools::{Combinations, Itertools};
-
-type SpaceExpansion = (HashSet<usize>, HashSet<usize>);
-
-fn get_expanded_rows_columns(space: &Lines) -> SpaceExpansion {
-    let mut rows_to_be_expanded: HashSet<usize> = HashSet::new();
-    let size = space.clone().count();
-    'rows: for (i, l) in space.clone().enumerate() {
-        if l.chars().any(|c| c == '#') {
-            continue 'rows;
-        };
-        rows_to_be_expanded.insert(i);
-    }
 
-    let mut columns_to_be_expanded: HashSet<usize> = HashSet::new();
-    'cols: for i in 0..size {
-        for row in space.clone() {
-            if row.as_bytes()[i] == b'#' {
-                continue 'cols;
-            }
+/// Number of galaxies in every row and column.
+struct Space {
+    rows: Vec<u64>,
+    columns: Vec<u64>,
+}
+
+fn parse_space(input: &str) -> Result<Space, String> {
+    let width = input.lines().next().map_or(0, str::len);
+    let mut space = Space {
+        rows: vec![],
+        columns: vec![0; width],
+    };
+
+    for (y, line) in input.lines().enumerate() {
+        if line.len() != width {
+            return Err(format!(
+                "Row {y} has {} cells, expected {width} like the first row",
+                line.len()
+            ));
         }
-        columns_to_be_expanded.insert(i);
+        let mut galaxies = 0;
+        for (x, _) in line.match_indices('#') {
+            space.columns[x] += 1;
+            galaxies += 1;
+        }
+        space.rows.push(galaxies);
     }
-    (rows_to_be_expanded, columns_to_be_expanded)
-}
 
-fn create_pairs(
-    space: Lines,
-    expansion: &SpaceExpansion,
-    expansion_factor: usize,
-) -> Combinations<IntoIter<Position>> {
-    let mut pairs: Vec<Position> = vec![];
-    space.enumerate().for_each(|(i, row)| {
-        let mut galaxy_row = row
-            .match_indices('#')
-            .map(|(j, _)| create_expanded_position((j, i), expansion, expansion_factor))
-            .collect();
-        pairs.append(&mut galaxy_row);
-    });
-    pairs.into_iter().combinations(2)
+    Ok(space)
 }
 
-fn create_expanded_position(
-    position: Position,
-    expansion: &SpaceExpansion,
-    expansion_factor: usize,
-) -> Position {
-    let (x, y) = position;
-    let x_expanded =
-        x + (expansion.1.iter().filter(|&&col_idx| col_idx < x).count() * (expansion_factor - 1));
-    let y_expanded =
-        y + (expansion.0.iter().filter(|&&row_idx| row_idx < y).count() * (expansion_factor - 1));
-
-    (x_expanded, y_expanded)
+fn get_expansion(counts: &[u64]) -> Expansion {
+    let occupied: Vec<bool> = counts.iter().map(|count| *count > 0).collect();
+    Expansion::new(&occupied)
 }
 
-fn get_expanded_distance(pair: &Vec<Position>) -> usize {
-    if pair.len() != 2 {
-        panic!("Incorrect pair length!");
-    }
-    Map::get_distance(pair[0], pair[1])
+/// Distances along one axis between all pairs of galaxies.
+/// Rows (or columns) are already in order, so the pairwise sum is linear.
+fn get_axis_distance(counts: &[u64], expansion_factor: u64) -> u64 {
+    let expansion = get_expansion(counts);
+    sum_pairwise_distances_sorted(
+        counts
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| **count > 0)
+            .map(|(i, count)| (expansion.expand(i, expansion_factor), *count)),
+    )
 }
 
-fn estimate_galaxy_size(input: &str, expansion_factor: usize) -> Option<u64> {
-    let space = input.lines();
-    let expanded_space = get_expanded_rows_columns(&space);
-    let pairs = create_pairs(space, &expanded_space, expansion_factor);
-    let distance = pairs.fold(0, |acc: usize, el| acc + get_expanded_distance(&el));
-    Some(distance as u64)
+fn estimate_galaxy_size(input: &str, expansion_factor: u64) -> Option<u64> {
+    let space = parse_space(input).map_err(|e| eprintln!("{e}")).ok()?;
+    // manhattan distances split into independent row and column distances.
+    Some(
+        get_axis_distance(&space.rows, expansion_factor)
+            + get_axis_distance(&space.columns, expansion_factor),
+    )
 }
+
 pub fn part_one(input: &str) -> Option<u64> {
     estimate_galaxy_size(input, 2)
 }
@@ -84,40 +71,48 @@ pub fn part_two(input: &str) -> Option<u64> {
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    fn create_expanded_position(space: &Space, position: Position, factor: u64) -> Position {
+        let (x, y) = position;
+        (
+            get_expansion(&space.columns).expand(x, factor) as usize,
+            get_expansion(&space.rows).expand(y, factor) as usize,
+        )
+    }
+
     #[test]
     fn test_expanded_distance() {
-        let expansion_factor = 2;
-        let expansion: SpaceExpansion = (HashSet::from([3, 7]), HashSet::from([2, 5, 8]));
-
-        let point_5 = create_expanded_position((1, 5), &expansion, expansion_factor);
-        let point_9 = create_expanded_position((4, 9), &expansion, expansion_factor);
-        let result = get_expanded_distance(&vec![point_5, point_9]);
-        assert_eq!(result, 9);
-
-        let point_1 = create_expanded_position((3, 0), &expansion, expansion_factor);
-        let point_7 = create_expanded_position((7, 8), &expansion, expansion_factor);
-        let result = get_expanded_distance(&vec![point_1, point_7]);
-        assert_eq!(result, 15);
-
-        let point_3 = create_expanded_position((0, 2), &expansion, expansion_factor);
-        let point_6 = create_expanded_position((9, 6), &expansion, expansion_factor);
-        let result = get_expanded_distance(&vec![point_3, point_6]);
-        assert_eq!(result, 17);
-
-        let point_8 = create_expanded_position((0, 9), &expansion, expansion_factor);
-        let result = get_expanded_distance(&vec![point_8, point_9]);
-        assert_eq!(result, 5);
+        let space = parse_space(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        let expand = |position| create_expanded_position(&space, position, 2);
+
+        let point_5 = expand((1, 5));
+        let point_9 = expand((4, 9));
+        assert_eq!(Map::get_distance(point_5, point_9), 9);
+
+        let point_1 = expand((3, 0));
+        let point_7 = expand((7, 8));
+        assert_eq!(Map::get_distance(point_1, point_7), 15);
+
+        let point_3 = expand((0, 2));
+        let point_6 = expand((9, 6));
+        assert_eq!(Map::get_distance(point_3, point_6), 17);
+
+        let point_8 = expand((0, 9));
+        assert_eq!(Map::get_distance(point_8, point_9), 5);
     }
 
     #[test]
     fn test_amount_of_pairs() {
-        let binding = advent_of_code::template::read_file("examples", DAY);
-        let space = binding.lines();
-        let expansion_factor = 1;
-        let expansion: SpaceExpansion = (HashSet::from([3, 7]), HashSet::from([2, 5, 8]));
-        let pairs = create_pairs(space, &expansion, expansion_factor);
-        let result = pairs.collect::<Vec<Vec<Position>>>().len();
-        assert_eq!(result, 36)
+        let space = parse_space(&advent_of_code::template::read_file("examples", DAY)).unwrap();
+        // 9 galaxies, so 36 pairs.
+        assert_eq!(space.rows, [1, 1, 1, 0, 1, 1, 1, 0, 1, 2]);
+        assert_eq!(space.columns, [2, 1, 0, 1, 1, 0, 1, 2, 0, 1]);
+    }
+
+    #[test]
+    fn test_uneven_rows() {
+        assert!(parse_space("#.\n..#").is_err());
+        assert_eq!(part_one("#.\n..#"), None);
     }
 
     #[test]
@@ -140,4 +135,3 @@ mod tests {
         assert_eq!(result, Some(8410));
     }
 }
-// #1 Part 1: 9723824 (75.1ms) Part 2: 1099821032 (69.3ms)
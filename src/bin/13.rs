@@ -1,93 +1,115 @@
 advent_of_code::solution!(13);
-use std::cmp::min;
-
-fn parse_input(input: &str) -> Vec<Vec<&str>> {
-    let mut mirrors = Vec::new();
-    let mut mirror = Vec::new();
-    input.lines().for_each(|l| {
-        if l.len() == 0 {
-            mirrors.push(mirror.clone());
-            mirror = Vec::new();
-        } else {
-            mirror.push(l);
-        }
-    });
-    mirrors.push(mirror);
-    mirrors
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq)]
+enum MirrorError {
+    TooWide { pattern: usize },
+    UnevenRows { pattern: usize },
+    NoReflection { pattern: usize, smudges: u32 },
 }
 
-fn find_horizontal_reflection(input: &Vec<&str>, smudges: usize) -> Option<usize> {
-    let depth = input.len() - 1;
-    'row: for i in 0..depth {
-        let mut smudges_count = 0;
-        let iterations = min(i + 1, depth - i);
-        for j in 0..iterations {
-            let top = input[i - j].as_bytes().iter();
-            let bottom = input[i + 1 + j].as_bytes().iter();
-            let comparison = top.zip(bottom);
-            for (top_char, bottom_char) in comparison {
-                if top_char != bottom_char {
-                    smudges_count += 1;
-                    if smudges_count > smudges {
-                        continue 'row;
-                    }
-                }
+impl fmt::Display for MirrorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MirrorError::TooWide { pattern } => {
+                write!(f, "Pattern {pattern} has more than 64 rows or columns")
             }
+            MirrorError::UnevenRows { pattern } => {
+                write!(f, "Pattern {pattern} has rows of different lengths")
+            }
+            MirrorError::NoReflection { pattern, smudges } => write!(
+                f,
+                "Pattern {pattern} has no reflection with {smudges} smudge(s)"
+            ),
         }
+    }
+}
+
+/// A pattern with every row and column packed into a bitmask, `#` being a set bit.
+#[derive(Debug)]
+struct Pattern {
+    rows: Vec<u64>,
+    columns: Vec<u64>,
+}
+
+/// `index` is the 1-based number of the pattern, used in errors.
+fn parse_pattern(index: usize, lines: &[&str]) -> Result<Pattern, MirrorError> {
+    let width = lines.first().map_or(0, |l| l.len());
+    if lines.iter().any(|l| l.len() != width) {
+        return Err(MirrorError::UnevenRows { pattern: index });
+    }
+    if lines.len() > 64 || width > 64 {
+        return Err(MirrorError::TooWide { pattern: index });
+    }
 
-        if smudges_count == smudges {
-            return Some(i + 1);
+    let mut rows = vec![0; lines.len()];
+    let mut columns = vec![0; width];
+    for (y, line) in lines.iter().enumerate() {
+        for (x, c) in line.bytes().enumerate() {
+            if c == b'#' {
+                rows[y] |= 1 << x;
+                columns[x] |= 1 << y;
+            }
         }
     }
-    None
+    Ok(Pattern { rows, columns })
+}
+
+/// Patterns are separated by blank lines.
+fn parse_input(input: &str) -> Result<Vec<Pattern>, MirrorError> {
+    let lines: Vec<&str> = input.lines().collect();
+    lines
+        .split(|l| l.is_empty())
+        .filter(|p| !p.is_empty())
+        .enumerate()
+        .map(|(i, p)| parse_pattern(i + 1, p))
+        .collect()
 }
 
-fn find_vertical_reflection(input: &Vec<&str>, smudges: usize) -> Option<usize> {
-    let width = input[0].len() - 1;
-    let depth = input.len();
-    'col: for i in 0..width {
-        let mut smudges_count = 0;
-        let iterations = min(i + 1, width - i);
-        for j in 0..depth {
-            let row = input[j].as_bytes();
-            for k in 0..iterations {
-                if row[i - k] != row[i + 1 + k] {
-                    smudges_count += 1;
-                    if smudges_count > smudges {
-                        continue 'col;
-                    }
-                }
+/// Finds the line of reflection with exactly `smudges` differing cells, shared by rows and columns.
+/// Returns the number of lines before it.
+fn find_reflection(lines: &[u64], smudges: u32) -> Option<usize> {
+    (1..lines.len()).find(|&i| {
+        let mut differences = 0;
+        for (before, after) in lines[..i].iter().rev().zip(&lines[i..]) {
+            differences += (before ^ after).count_ones();
+            if differences > smudges {
+                return false;
             }
         }
+        differences == smudges
+    })
+}
 
-        if smudges_count == smudges {
-            return Some(i + 1);
-        }
-    }
-    None
+fn solve(input: &str, smudges: u32) -> Result<usize, MirrorError> {
+    parse_input(input)?
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            if let Some(rows_above) = find_reflection(&p.rows, smudges) {
+                Ok(100 * rows_above)
+            } else if let Some(cols_left) = find_reflection(&p.columns, smudges) {
+                Ok(cols_left)
+            } else {
+                Err(MirrorError::NoReflection {
+                    pattern: i + 1,
+                    smudges,
+                })
+            }
+        })
+        .sum()
 }
 
-fn solve(input: &str, smudges: usize) -> Option<usize> {
-    let mirrors = parse_input(input);
-    let mut sum = 0;
-    mirrors.iter().for_each(|m| {
-        if let Some(rows_above) = find_horizontal_reflection(m, smudges) {
-            sum += 100 * rows_above;
-        } else if let Some(cols_left) = find_vertical_reflection(m, smudges) {
-            sum += cols_left;
-        } else {
-            panic!("No pattern found!")
-        }
-    });
-    Some(sum)
+fn report(result: Result<usize, MirrorError>) -> Option<usize> {
+    result.map_err(|e| eprintln!("{e}")).ok()
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    solve(input, 0)
+    report(solve(input, 0))
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    solve(input, 1)
+    report(solve(input, 1))
 }
 
 #[cfg(test)]
@@ -130,6 +152,43 @@ mod tests {
         assert_eq!(result, Some(5))
     }
 
+    #[test]
+    fn test_find_reflection() {
+        let lines = [0b0110, 0b1001, 0b1001, 0b0110];
+        assert_eq!(find_reflection(&lines, 0), Some(2));
+        assert_eq!(find_reflection(&[0b01, 0b10], 0), None);
+        assert_eq!(find_reflection(&[0b01, 0b11], 1), Some(1));
+    }
+
+    #[test]
+    fn test_no_reflection() {
+        let result = solve("#.#\n.##\n#..", 0);
+        assert_eq!(
+            result,
+            Err(MirrorError::NoReflection {
+                pattern: 1,
+                smudges: 0
+            })
+        );
+        assert_eq!(part_one("#.#\n.##\n#.."), None);
+    }
+
+    #[test]
+    fn test_blank_lines() {
+        let input = advent_of_code::template::read_file("examples", DAY);
+        assert_eq!(solve(&input.replace('\n', "\r\n"), 0), Ok(405));
+        assert_eq!(solve(&format!("\n{input}\n\n\n"), 0), Ok(405));
+    }
+
+    #[test]
+    fn test_uneven_rows() {
+        let result = solve("#.#\n.##\n\n##\n#.##", 0);
+        assert_eq!(result.err(), Some(MirrorError::UnevenRows { pattern: 2 }));
+
+        let wide = format!("#.\n{}", "#".repeat(70));
+        assert_eq!(part_one(&wide), None);
+    }
+
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
@@ -142,6 +201,3 @@ mod tests {
         assert_eq!(result, Some(400));
     }
 }
-
-// #1: Part 1: 37113 (30.7ms)
-// #2 Part 1: 37113 (1.9ms) && Part 2: 30449 (2.1ms)
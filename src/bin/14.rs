@@ -1,211 +1,205 @@
 use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hasher};
 
-advent_of_code::solution!(14);
-
-type RockColumn = Vec<char>;
-type Rocks = Vec<RockColumn>;
-
-trait Platform {
-    fn slide(self, normal: bool) -> Self;
-    fn slide_north(self) -> Self;
-    fn slide_east(self) -> Self;
-    fn slide_south(self) -> Self;
-    fn slide_west(self) -> Self;
-    fn cycle(self) -> Self;
-    fn transpose(self) -> Self;
-    fn calculate_load(&self) -> usize;
-}
+use advent_of_code::utils::map::Direction;
 
-impl Platform for Rocks {
-    fn transpose(self) -> Self {
-        let mut transposed: Rocks = vec![Vec::new(); self[0].len()];
-        self.into_iter().for_each(|rock_row| {
-            rock_row.into_iter().enumerate().for_each(|(i, c)| {
-                transposed[i].push(c);
-            })
-        });
-        transposed
-    }
+advent_of_code::solution!(14);
 
-    fn slide(self, normal: bool) -> Self {
-        let mut result = vec![];
-        self.into_iter().for_each(|row| {
-            let mut sorted_row: Vec<char> = Vec::with_capacity(row.len());
-            let free_cols = row.split(|c| *c == '#');
-            free_cols.into_iter().for_each(|cols| {
-                let mut col_vec = cols.to_vec();
-                col_vec.sort_by(|a, b| if normal { a.cmp(b) } else { b.cmp(a) });
-                sorted_row.extend(col_vec);
-                sorted_row.push('#');
-            });
-            sorted_row.pop();
-            result.push(sorted_row)
-        });
-        result
-    }
+const ROUND: u8 = b'O';
+const CUBE: u8 = b'#';
+const EMPTY: u8 = b'.';
 
-    fn slide_north(self) -> Self {
-        self.transpose().slide(false).transpose()
-    }
+/// The platform as a flat, row-major byte grid, tilted in place.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Dish {
+    width: usize,
+    height: usize,
+    cells: Vec<u8>,
+}
 
-    fn slide_east(self) -> Self {
-        self.slide(true)
+impl Dish {
+    /// Rolls every round rock as far as possible towards `direction`.
+    /// Each lane (a column when tilting north or south, a row otherwise) is walked from the
+    /// side the rocks roll to, keeping track of the next free cell.
+    fn tilt(&mut self, direction: Direction) {
+        let (lanes, length) = match direction {
+            Direction::Up | Direction::Down => (self.width, self.height),
+            Direction::Left | Direction::Right => (self.height, self.width),
+        };
+        let index = |lane: usize, step: usize| match direction {
+            Direction::Up => step * self.width + lane,
+            Direction::Down => (self.height - 1 - step) * self.width + lane,
+            Direction::Left => lane * self.width + step,
+            Direction::Right => lane * self.width + self.width - 1 - step,
+        };
+
+        for lane in 0..lanes {
+            let mut free = 0;
+            for step in 0..length {
+                match self.cells[index(lane, step)] {
+                    CUBE => free = step + 1,
+                    ROUND => {
+                        if free != step {
+                            self.cells[index(lane, free)] = ROUND;
+                            self.cells[index(lane, step)] = EMPTY;
+                        }
+                        free += 1;
+                    }
+                    _ => {}
+                }
+            }
+        }
     }
 
-    fn slide_south(self) -> Self {
-        self.transpose().slide(true).transpose()
+    fn cycle(&mut self) {
+        for direction in [
+            Direction::Up,
+            Direction::Left,
+            Direction::Down,
+            Direction::Right,
+        ] {
+            self.tilt(direction);
+        }
     }
 
-    fn slide_west(self) -> Self {
-        self.slide(false)
+    fn round_rocks(&self) -> impl Iterator<Item = usize> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| **c == ROUND)
+            .map(|(i, _)| i)
     }
 
-    fn cycle(self) -> Self {
-        self.slide_north().slide_west().slide_south().slide_east()
+    /// Hash of the round-rock positions, the only part of the platform that changes.
+    fn state_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        for i in self.round_rocks() {
+            hasher.write_usize(i);
+        }
+        hasher.finish()
     }
 
     fn calculate_load(&self) -> usize {
-        let mut load = 0;
-        let length = self.len();
-        self.iter().enumerate().for_each(|(i, rock_row)| {
-            rock_row.iter().for_each(|rock| {
-                if *rock == 'O' {
-                    load += length - i;
-                }
-            });
-        });
-        load
+        self.round_rocks()
+            .map(|i| self.height - i / self.width)
+            .sum()
     }
 }
 
-fn parse_input(input: &str) -> Vec<RockColumn> {
-    let mut rocks: Vec<RockColumn> = Vec::new();
-    input.lines().for_each(|l| {
-        let row = l.chars().collect();
-        rocks.push(row);
-    });
-    rocks
+fn parse_input(input: &str) -> Dish {
+    let lines: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+    Dish {
+        width: lines.first().map_or(0, |l| l.len()),
+        height: lines.len(),
+        cells: lines.concat(),
+    }
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let mut rocks = parse_input(input);
-    rocks = rocks.slide_north();
-    Some(rocks.calculate_load())
-}
-
-fn calculate_remaining_cycles(mut rocks: Rocks, remaining_iterations: i32) -> usize {
-    for _ in 0..remaining_iterations {
-        rocks = rocks.cycle();
-    }
-    rocks.calculate_load()
+    let mut dish = parse_input(input);
+    dish.tilt(Direction::Up);
+    Some(dish.calculate_load())
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let mut rocks = parse_input(input);
-    let mut hash_map: HashMap<Rocks, i32> = HashMap::new();
+    let mut dish = parse_input(input);
     let iterations = 1_000_000_000;
+
+    // the load and round-rock positions after each cycle, looked up by their hash.
+    // hashes may collide, so a hit only counts if the positions match as well.
+    let mut loads = vec![dish.calculate_load()];
+    let mut states: Vec<Vec<usize>> = vec![dish.round_rocks().collect()];
+    let mut seen: HashMap<u64, Vec<usize>> = HashMap::from([(dish.state_hash(), vec![0])]);
     for i in 1..=iterations {
-        hash_map.insert(rocks.clone(), i);
-        rocks = rocks.cycle();
-        if let Some(counter) = hash_map.get(&rocks) {
-            let remaining_iterations = (iterations - i) % (i - counter + 1);
-            return Some(calculate_remaining_cycles(rocks, remaining_iterations));
+        dish.cycle();
+        loads.push(dish.calculate_load());
+        let state: Vec<usize> = dish.round_rocks().collect();
+        let candidates = seen.entry(dish.state_hash()).or_default();
+        if let Some(&start) = candidates.iter().find(|&&j| states[j] == state) {
+            let period = i - start;
+            return Some(loads[start + (iterations - start) % period]);
         }
+        candidates.push(i);
+        states.push(state);
     }
-    panic!("Shouldn't reach end of code!")
+    loads.last().copied()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_transpose() {
-        let rocks: Rocks = vec![
-            vec!['a', 'b', 'c'],
-            vec!['d', 'e', 'f'],
-            vec!['g', 'h', 'i'],
-        ];
-        let expected: Rocks = vec![
-            vec!['a', 'd', 'g'],
-            vec!['b', 'e', 'h'],
-            vec!['c', 'f', 'i'],
-        ];
-        let result = rocks.clone().transpose();
-        assert_eq!(result, expected);
+    fn tilted(row: &str, direction: Direction) -> Dish {
+        let mut dish = parse_input(row);
+        dish.tilt(direction);
+        dish
     }
 
     #[test]
-    fn test_transpose_twice() {
-        let rocks: Rocks = vec![
-            vec!['a', 'b', 'c'],
-            vec!['d', 'e', 'f'],
-            vec!['g', 'h', 'i'],
-        ];
-        let result = rocks.clone().transpose().transpose();
-        assert_eq!(result, rocks);
+    fn test_north_slide() {
+        let result = tilted("O\nO\n.\nO\n.\nO\n.\n.\n#\n#", Direction::Up);
+        assert_eq!(result, parse_input("O\nO\nO\nO\n.\n.\n.\n.\n#\n#"));
     }
 
     #[test]
-    fn test_sorting() {
-        let rocks: Rocks = vec![vec!['O', 'O', '.', 'O', '.', 'O', '.', '.', '#', '#']];
-        let result = rocks.slide(false);
-        assert_eq!(
-            result,
-            vec![vec!['O', 'O', 'O', 'O', '.', '.', '.', '.', '#', '#']]
-        )
+    fn test_south_slide() {
+        let result = tilted("O\n#\n.\nO\n.\nO\n.\n#", Direction::Down);
+        assert_eq!(result, parse_input("O\n#\n.\n.\n.\nO\nO\n#"));
     }
 
     #[test]
     fn test_east_slide() {
-        let rocks: Rocks = vec![vec!['O', 'O', '.', 'O', '.', 'O', '.', '.', '#', '#']];
-        let result = rocks.slide_east();
-        assert_eq!(
-            result,
-            vec![vec!['.', '.', '.', '.', 'O', 'O', 'O', 'O', '#', '#']]
-        )
+        let result = tilted("OO.O.O..##", Direction::Right);
+        assert_eq!(result, parse_input("....OOOO##"));
     }
 
     #[test]
     fn test_west_slide() {
-        let rocks: Rocks = vec![vec!['O', 'O', '.', 'O', '.', 'O', '.', '.', '#', '#']];
-        let result = rocks.slide_west();
-        assert_eq!(
-            result,
-            vec![vec!['O', 'O', 'O', 'O', '.', '.', '.', '.', '#', '#']]
-        )
+        let result = tilted("OO.O.O..##", Direction::Left);
+        assert_eq!(result, parse_input("OOOO....##"));
     }
 
     #[test]
     fn test_load() {
-        let rocks: Rocks = vec![vec!['O', 'O', 'O', 'O', '.', '.', '.', '.', '#', '#']];
-        let result = rocks.calculate_load();
-        assert_eq!(result, 4)
+        let dish = parse_input("O\nO\nO\nO\n.\n.\n.\n.\n#\n#");
+        assert_eq!(dish.calculate_load(), 34);
+    }
+
+    #[test]
+    fn test_state_hash() {
+        let dish = parse_input("O.#\n...");
+        assert_eq!(dish.state_hash(), dish.clone().state_hash());
+        assert_ne!(
+            dish.state_hash(),
+            tilted("O.#\n...", Direction::Down).state_hash()
+        );
     }
 
     #[test]
     fn test_one_cycle() {
-        let rocks: Rocks = parse_input(&advent_of_code::template::read_file("examples", DAY));
-        let result = rocks.cycle();
-        let expected_result = vec![
-            vec!['.', '.', '.', '.', '.', '#', '.', '.', '.', '.'],
-            vec!['.', '.', '.', '.', '#', '.', '.', '.', 'O', '#'],
-            vec!['.', '.', '.', 'O', 'O', '#', '#', '.', '.', '.'],
-            vec!['.', 'O', 'O', '#', '.', '.', '.', '.', '.', '.'],
-            vec!['.', '.', '.', '.', '.', 'O', 'O', 'O', '#', '.'],
-            vec!['.', 'O', '#', '.', '.', '.', 'O', '#', '.', '#'],
-            vec!['.', '.', '.', '.', 'O', '#', '.', '.', '.', '.'],
-            vec!['.', '.', '.', '.', '.', '.', 'O', 'O', 'O', 'O'],
-            vec!['#', '.', '.', '.', 'O', '#', '#', '#', '.', '.'],
-            vec!['#', '.', '.', 'O', 'O', '#', '.', '.', '.', '.'],
-        ];
-        assert_eq!(result, expected_result);
+        let mut dish = parse_input(&advent_of_code::template::read_file("examples", DAY));
+        dish.cycle();
+        let expected_result = parse_input(
+            ".....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#....",
+        );
+        assert_eq!(dish, expected_result);
     }
 
     #[test]
     fn test_three_cycles() {
-        let rocks: Rocks = parse_input(&advent_of_code::template::read_file("examples", DAY));
-        let result = rocks.cycle().cycle().cycle();
+        let mut dish = parse_input(&advent_of_code::template::read_file("examples", DAY));
+        for _ in 0..3 {
+            dish.cycle();
+        }
         let expected_result = parse_input(
             ".....#....
 ....#...O#
@@ -218,7 +212,7 @@ mod tests {
 #...O###.O
 #.OOO#...O",
         );
-        assert_eq!(result, expected_result);
+        assert_eq!(dish, expected_result);
     }
 
     #[test]
@@ -233,5 +227,3 @@ mod tests {
         assert_eq!(result, Some(64));
     }
 }
-
-// Part 1: 108144 (9.3ms) && Part 2: 108404 (2.8s)
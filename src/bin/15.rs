@@ -112,5 +112,3 @@ mod tests {
         assert_eq!(result, Some(145));
     }
 }
-
-// Part 1: 514281 (2.7ms) && Part 2: 244199 (7.2ms)
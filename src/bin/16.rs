@@ -1,30 +1,8 @@
-advent_of_code::solution!(16);
-use advent_of_code::utils::map::*;
-use rayon::iter::*;
-use advent_of_code::utils::map::*;
-
-type CaveMap = Vec<Vec<Space>>;
-
-trait Navigation {
-    fn split_vertical(&mut self, pos: Position);
-    fn split_horizontal(&mut self, pos: Position);
-    fn visit_pos(&mut self, pos: Position, direction: Direction) -> Result<bool, &str>;
-    fn light_left_map(&self, pos: Position) -> bool;
-    fn move_down(&mut self, prev_pos: Position);
-    fn move_up(&mut self, prev_pos: Position);
-    fn move_right(&mut self, prev_pos: Position);
-    fn move_left(&mut self, prev_pos: Position);
-    fn count_visited(&self) -> usize;
-    fn get_space(&self, pos: &Position) -> &Space;
-}
-
-#[derive(Copy, Clone, Debug)]
-struct Position {
-    x: isize,
-    y: isize,
-}
+advent_of_code::solution!(16, Day16);
+use advent_of_code::template::Solution;
+use advent_of_code::utils::map::Direction;
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 enum SpaceType {
     Empty,
     HorizontalSplitter,
@@ -33,233 +11,352 @@ enum SpaceType {
     LeftDownMirror,
 }
 
-#[derive(Copy, Clone)]
-struct Space {
-    space_type: SpaceType,
-    visited: [bool; 4],
-}
-
 impl SpaceType {
-    fn from_char(c: char) -> Option<Space> {
-        if let Some(space_type) = match c {
+    fn from_char(c: char) -> Option<SpaceType> {
+        match c {
             '.' => Some(SpaceType::Empty),
             '-' => Some(SpaceType::HorizontalSplitter),
             '|' => Some(SpaceType::VerticalSplitter),
             '/' => Some(SpaceType::LeftRightMirror),
             '\\' => Some(SpaceType::LeftDownMirror),
             _ => None,
-        } {
-            return Some(Space {
-                space_type,
-                visited: [false; 4],
-            });
         }
-        None
     }
-}
 
-impl Navigation for CaveMap {
-    fn light_left_map(&self, pos: Position) -> bool {
-        let max_rows = self.len() as isize;
-        let max_cols = self[0].len() as isize;
-        pos.x >= max_cols || pos.y >= max_rows || pos.x < 0 || pos.y < 0
+    /// Directions a beam continues in after entering this space, the second one only for splits.
+    fn outgoing(self, direction: Direction) -> (Direction, Option<Direction>) {
+        use Direction::*;
+        match (self, direction) {
+            (SpaceType::HorizontalSplitter, Up | Down) => (Left, Some(Right)),
+            (SpaceType::VerticalSplitter, Left | Right) => (Up, Some(Down)),
+            (SpaceType::LeftRightMirror, Right) => (Up, None),
+            (SpaceType::LeftRightMirror, Up) => (Right, None),
+            (SpaceType::LeftRightMirror, Left) => (Down, None),
+            (SpaceType::LeftRightMirror, Down) => (Left, None),
+            (SpaceType::LeftDownMirror, Right) => (Down, None),
+            (SpaceType::LeftDownMirror, Down) => (Right, None),
+            (SpaceType::LeftDownMirror, Left) => (Up, None),
+            (SpaceType::LeftDownMirror, Up) => (Left, None),
+            _ => (direction, None),
+        }
     }
+}
 
-    fn get_space(&self, pos: &Position) -> &Space {
-        &self[pos.y as usize][pos.x as usize]
+/// The contraption as a flat, row-major grid.
+#[derive(Clone, Debug)]
+struct Cave {
+    width: usize,
+    height: usize,
+    spaces: Vec<SpaceType>,
+}
+
+impl Cave {
+    /// The neighbouring cell in `direction`, `None` if the beam leaves the map.
+    fn step(&self, cell: usize, direction: Direction) -> Option<usize> {
+        let (x, y) = (cell % self.width, cell / self.width);
+        match direction {
+            Direction::Up => (y > 0).then(|| cell - self.width),
+            Direction::Down => (y + 1 < self.height).then(|| cell + self.width),
+            Direction::Left => (x > 0).then(|| cell - 1),
+            Direction::Right => (x + 1 < self.width).then(|| cell + 1),
+        }
     }
 
-    fn move_down(&mut self, prev_pos: Position) {
-        let next_pos = Position {
-            x: prev_pos.x,
-            y: prev_pos.y + 1,
-        };
+    /// Number of cells a beam entering `start` in `direction` energizes.
+    /// Beams are kept on a work queue, a bitmask per cell remembers the directions already passed.
+    fn energize(&self, start: usize, direction: Direction) -> usize {
+        let mut beams = vec![0_u8; self.spaces.len()];
+        let mut queue = vec![(start, direction)];
+        let mut energized = 0;
+
+        while let Some((cell, direction)) = queue.pop() {
+            let bit = 1 << usize::from(direction);
+            if beams[cell] & bit != 0 {
+                continue;
+            }
+            if beams[cell] == 0 {
+                energized += 1;
+            }
+            beams[cell] |= bit;
 
-        if self.visit_pos(next_pos, Direction::Down).is_err() {
-            return;
+            let (first, second) = self.spaces[cell].outgoing(direction);
+            for next_direction in [Some(first), second].into_iter().flatten() {
+                if let Some(next) = self.step(cell, next_direction) {
+                    queue.push((next, next_direction));
+                }
+            }
         }
+        energized
+    }
 
-        match self.get_space(&next_pos).space_type {
-            SpaceType::Empty | SpaceType::VerticalSplitter => self.move_down(next_pos),
-            SpaceType::HorizontalSplitter => self.split_horizontal(next_pos),
-            SpaceType::LeftRightMirror => self.move_left(next_pos),
-            SpaceType::LeftDownMirror => self.move_right(next_pos),
+    /// Entry points of every beam coming in from the edge.
+    fn edge_entries(&self) -> Vec<(usize, Direction)> {
+        let last_row = (self.height - 1) * self.width;
+        let mut entries = vec![];
+        for x in 0..self.width {
+            entries.push((x, Direction::Down));
+            entries.push((last_row + x, Direction::Up));
+        }
+        for y in 0..self.height {
+            entries.push((y * self.width, Direction::Right));
+            entries.push((y * self.width + self.width - 1, Direction::Left));
         }
+        entries
     }
+}
 
-    fn move_up(&mut self, prev_pos: Position) {
-        let next_pos = Position {
-            x: prev_pos.x,
-            y: prev_pos.y - 1,
-        };
+fn parse_input(input: &str) -> Cave {
+    let lines: Vec<&str> = input.lines().collect();
+    Cave {
+        width: lines.first().map_or(0, |l| l.len()),
+        height: lines.len(),
+        spaces: lines
+            .iter()
+            .flat_map(|l| l.chars().map(|c| SpaceType::from_char(c).unwrap()))
+            .collect(),
+    }
+}
 
-        if self.visit_pos(next_pos, Direction::Up).is_err() {
-            return;
-        }
+/* -------------------------------------------------------------------------- */
+
+/// Cells a beam passes until it is split or leaves the map.
+struct Segment {
+    cells: Vec<usize>,
+    /// The splitter that ends the segment.
+    target: Option<usize>,
+}
+
+fn set_bit(bits: &mut [u64], i: usize) {
+    bits[i / 64] |= 1 << (i % 64);
+}
+
+/// Beams only branch at splitters and follow mirrors deterministically in between.
+/// Every splitter is a node with the two segments it sends out, and the cells reachable from
+/// a node are shared by every beam that hits it. They are computed once per strongly connected
+/// component, so each edge entry only traces its way to the first splitter.
+struct SegmentGraph {
+    /// Node index of every splitter cell.
+    nodes: Vec<Option<usize>>,
+    component: Vec<usize>,
+    /// Cells energized from each component, as a bitset over the grid.
+    reach: Vec<Vec<u64>>,
+    words: usize,
+}
 
-        match self.get_space(&next_pos).space_type {
-            SpaceType::Empty | SpaceType::VerticalSplitter => self.move_up(next_pos),
-            SpaceType::HorizontalSplitter => self.split_horizontal(next_pos),
-            SpaceType::LeftRightMirror => self.move_right(next_pos),
-            SpaceType::LeftDownMirror => self.move_left(next_pos),
+impl SegmentGraph {
+    fn new(cave: &Cave) -> Self {
+        let mut nodes = vec![None; cave.spaces.len()];
+        let mut splitters = vec![];
+        for (cell, space) in cave.spaces.iter().enumerate() {
+            let split = match space {
+                SpaceType::HorizontalSplitter => [Direction::Left, Direction::Right],
+                SpaceType::VerticalSplitter => [Direction::Up, Direction::Down],
+                _ => continue,
+            };
+            nodes[cell] = Some(splitters.len());
+            splitters.push((cell, split));
         }
-    }
 
-    fn move_right(&mut self, prev_pos: Position) {
-        let next_pos = Position {
-            x: prev_pos.x + 1,
-            y: prev_pos.y,
-        };
+        let segments: Vec<Vec<Segment>> = splitters
+            .iter()
+            .map(|(cell, split)| {
+                split
+                    .iter()
+                    .map(|direction| match cave.step(*cell, *direction) {
+                        Some(next) => trace(cave, &nodes, next, *direction),
+                        None => Segment {
+                            cells: vec![],
+                            target: None,
+                        },
+                    })
+                    .collect()
+            })
+            .collect();
+        let edges: Vec<Vec<usize>> = segments
+            .iter()
+            .map(|s| s.iter().filter_map(|s| s.target).collect())
+            .collect();
+
+        let (component, count) = strongly_connected_components(&edges);
+        let mut members = vec![vec![]; count];
+        for (node, c) in component.iter().enumerate() {
+            members[*c].push(node);
+        }
 
-        if self.visit_pos(next_pos, Direction::Right).is_err() {
-            return;
+        // components are numbered in topological order, so successors are done first.
+        let words = cave.spaces.len().div_ceil(64);
+        let mut reach = vec![vec![0_u64; words]; count];
+        for c in (0..count).rev() {
+            let mut bits = vec![0_u64; words];
+            for node in &members[c] {
+                set_bit(&mut bits, splitters[*node].0);
+                for segment in &segments[*node] {
+                    segment
+                        .cells
+                        .iter()
+                        .for_each(|cell| set_bit(&mut bits, *cell));
+                    if let Some(target) = segment.target {
+                        if component[target] != c {
+                            bits.iter_mut()
+                                .zip(&reach[component[target]])
+                                .for_each(|(b, r)| *b |= r);
+                        }
+                    }
+                }
+            }
+            reach[c] = bits;
         }
 
-        match self.get_space(&next_pos).space_type {
-            SpaceType::Empty | SpaceType::HorizontalSplitter => self.move_right(next_pos),
-            SpaceType::VerticalSplitter => self.split_vertical(next_pos),
-            SpaceType::LeftRightMirror => self.move_up(next_pos),
-            SpaceType::LeftDownMirror => self.move_down(next_pos),
+        SegmentGraph {
+            nodes,
+            component,
+            reach,
+            words,
         }
     }
 
-    fn move_left(&mut self, prev_pos: Position) {
-        let next_pos = Position {
-            x: prev_pos.x - 1,
-            y: prev_pos.y,
+    fn energize(&self, cave: &Cave, start: usize, direction: Direction) -> usize {
+        let segment = trace(cave, &self.nodes, start, direction);
+        let mut bits = match segment.target {
+            Some(target) => self.reach[self.component[target]].clone(),
+            None => vec![0; self.words],
         };
+        segment
+            .cells
+            .iter()
+            .for_each(|cell| set_bit(&mut bits, *cell));
+        bits.iter().map(|b| b.count_ones() as usize).sum()
+    }
+}
 
-        if self.visit_pos(next_pos, Direction::Left).is_err() {
-            return;
+/// Follows a beam entering `start` in `direction` through mirrors until it reaches a splitter
+/// it is split by or leaves the map.
+fn trace(cave: &Cave, nodes: &[Option<usize>], start: usize, direction: Direction) -> Segment {
+    let mut cells = vec![];
+    let (mut cell, mut direction) = (start, direction);
+    let initial = (start, direction);
+    loop {
+        cells.push(cell);
+        let (next_direction, split) = cave.spaces[cell].outgoing(direction);
+        if split.is_some() {
+            return Segment {
+                cells,
+                target: nodes[cell],
+            };
         }
-
-        match self.get_space(&next_pos).space_type {
-            SpaceType::Empty | SpaceType::HorizontalSplitter => self.move_left(next_pos),
-            SpaceType::VerticalSplitter => self.split_vertical(next_pos),
-            SpaceType::LeftRightMirror => self.move_down(next_pos),
-            SpaceType::LeftDownMirror => self.move_up(next_pos),
+        direction = next_direction;
+        match cave.step(cell, direction) {
+            // beams are reversible, so a loop always returns to where it started.
+            Some(next) if (next, direction) == initial => break,
+            Some(next) => cell = next,
+            None => break,
         }
     }
-
-    fn count_visited(&self) -> usize {
-        let mut count = 0;
-        self.iter().for_each(|rows| {
-            rows.iter().for_each(|s| {
-                if s.visited.iter().any(|v| *v) {
-                    count += 1;
-                }
-            })
-        });
-        count
+    Segment {
+        cells,
+        target: None,
     }
+}
 
-    fn visit_pos(&mut self, pos: Position, direction: Direction) -> Result<bool, &str> {
-        if self.light_left_map(pos) {
-            return Err("Beam left the map!");
+/// Kosaraju's algorithm with explicit stacks. Components are numbered in topological order.
+fn strongly_connected_components(edges: &[Vec<usize>]) -> (Vec<usize>, usize) {
+    let n = edges.len();
+    let mut order = Vec::with_capacity(n);
+    let mut visited = vec![false; n];
+    for root in 0..n {
+        if visited[root] {
+            continue;
         }
-
-        let direction = direction as usize;
-        if self.get_space(&pos).visited[direction] {
-            return Err("Already visited this position");
+        visited[root] = true;
+        let mut stack = vec![(root, 0)];
+        while let Some((node, i)) = stack.last_mut() {
+            if let Some(next) = edges[*node].get(*i).copied() {
+                *i += 1;
+                if !visited[next] {
+                    visited[next] = true;
+                    stack.push((next, 0));
+                }
+            } else {
+                order.push(*node);
+                stack.pop();
+            }
         }
-        self[pos.y as usize][pos.x as usize].visited[direction] = true;
-        Ok(true)
     }
 
-    fn split_horizontal(&mut self, pos: Position) {
-        self.move_right(pos);
-        self.move_left(pos);
+    let mut reversed = vec![vec![]; n];
+    for (node, targets) in edges.iter().enumerate() {
+        targets.iter().for_each(|t| reversed[*t].push(node));
     }
 
-    fn split_vertical(&mut self, pos: Position) {
-        self.move_down(pos);
-        self.move_up(pos);
+    let mut component = vec![usize::MAX; n];
+    let mut count = 0;
+    for root in order.into_iter().rev() {
+        if component[root] != usize::MAX {
+            continue;
+        }
+        component[root] = count;
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            for previous in &reversed[node] {
+                if component[*previous] == usize::MAX {
+                    component[*previous] = count;
+                    stack.push(*previous);
+                }
+            }
+        }
+        count += 1;
     }
+    (component, count)
 }
 
-fn parse_input(input: &str) -> CaveMap {
-    let mut map: CaveMap = vec![];
-    input.lines().for_each(|s| {
-        let mut row = vec![];
-        s.chars()
-            .for_each(|c| row.push(SpaceType::from_char(c).unwrap()));
-        map.push(row);
-    });
-    map
-}
-
-pub fn part_one(input: &str) -> Option<usize> {
-    let mut map = parse_input(input);
-    let starting_pos = Position { x: -1, y: 0 };
-    map.move_right(starting_pos);
-    Some(map.count_visited())
-}
+/* -------------------------------------------------------------------------- */
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let map = parse_input(input);
-    [start_horizontal, start_vertical]
-        .into_par_iter()
-        .map(|f| f(map.clone()).unwrap())
-        .max()
-}
+struct Day16;
 
-fn start_horizontal(map: CaveMap) -> Option<usize> {
-    let cols = map.len() as isize;
-    let x = map[0].len() as isize;
-    [-1, x]
-        .into_par_iter()
-        .map(|x| find_max_horizontal(map.clone(), cols, x).unwrap())
-        .max()
-}
+impl Solution for Day16 {
+    type Parsed = Cave;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-fn start_vertical(map: CaveMap) -> Option<usize> {
-    let rows = map[0].len() as isize;
-    let y = map.len() as isize;
-    [-1, y]
-        .into_par_iter()
-        .map(|y| find_max_vertical(map.clone(), rows, y).unwrap())
-        .max()
-}
+    fn parse(input: &str) -> Cave {
+        parse_input(input)
+    }
 
-fn find_max_horizontal(map: CaveMap, cols: isize, x: isize) -> Option<usize> {
-    (0..cols)
-        .into_par_iter()
-        .map(|y| {
-            let mut map_clone = map.clone();
-            let starting_pos = Position { x, y };
-            if x == -1 {
-                map_clone.move_right(starting_pos);
-            } else {
-                map_clone.move_left(starting_pos);
-            }
-            map_clone.count_visited()
-        })
-        .max()
-}
+    fn part_one(cave: &Cave) -> Option<usize> {
+        if cave.spaces.is_empty() {
+            return None;
+        }
+        Some(cave.energize(0, Direction::Right))
+    }
 
-fn find_max_vertical(map: CaveMap, rows: isize, y: isize) -> Option<usize> {
-    (0..rows)
-        .into_par_iter()
-        .map(|x| {
-            let mut map_clone = map.clone();
-            let starting_pos = Position { x, y };
-            if y == -1 {
-                map_clone.move_down(starting_pos);
-            } else {
-                map_clone.move_up(starting_pos);
-            }
-            map_clone.count_visited()
-        })
-        .max()
+    fn part_two(cave: &Cave) -> Option<usize> {
+        if cave.spaces.is_empty() {
+            return None;
+        }
+        let graph = SegmentGraph::new(cave);
+        cave.edge_entries()
+            .into_iter()
+            .map(|(cell, direction)| graph.energize(cave, cell, direction))
+            .max()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::utils::testing::Rng;
+
+    fn solve_part_one(input: &str) -> Option<usize> {
+        Day16::part_one(&Day16::parse(input))
+    }
+
+    fn solve_part_two(input: &str) -> Option<usize> {
+        Day16::part_two(&Day16::parse(input))
+    }
 
     #[test]
     fn test_split() {
-        let result = part_one(
+        let result = solve_part_one(
             ".|.
 |.-",
         );
@@ -268,7 +365,7 @@ mod tests {
 
     #[test]
     fn test_infinite_iteration() {
-        let result = part_one(
+        let result = solve_part_one(
             ".-..\\
 .|.-/
 .|...
@@ -279,7 +376,7 @@ mod tests {
 
     #[test]
     fn test_new_line() {
-        let result = part_one(
+        let result = solve_part_one(
             r"\-..\
 .|.-/
 .|...
@@ -288,18 +385,60 @@ mod tests {
         assert_eq!(result, Some(4));
     }
 
+    #[test]
+    fn test_segment_graph_matches_work_queue() {
+        let mut rng = Rng::new(16);
+        for _ in 0..200 {
+            let (width, height) = (rng.range(1, 12) as usize, rng.range(1, 12) as usize);
+            let input: Vec<String> = (0..height)
+                .map(|_| {
+                    (0..width)
+                        .map(|_| *rng.choose(&['.', '.', '.', '-', '|', '/', '\\']))
+                        .collect()
+                })
+                .collect();
+            let cave = parse_input(&input.join("\n"));
+            let graph = SegmentGraph::new(&cave);
+            for (cell, direction) in cave.edge_entries() {
+                assert_eq!(
+                    graph.energize(&cave, cell, direction),
+                    cave.energize(cell, direction),
+                    "{input:?} {cell} {direction:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_long_beam() {
+        // a single beam zig-zagging through every row of a large grid.
+        let size = 1000;
+        let input: Vec<String> = (0..size)
+            .map(|y| {
+                let mut row = vec!['.'; size];
+                if y % 2 == 0 {
+                    row[size - 1] = '\\';
+                    row[0] = if y == 0 { '.' } else { '\\' };
+                } else {
+                    row[size - 1] = '/';
+                    row[0] = '/';
+                }
+                row.into_iter().collect()
+            })
+            .collect();
+        let result = solve_part_one(&input.join("\n"));
+        assert_eq!(result, Some(size * size));
+    }
+
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = solve_part_one(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(46));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = solve_part_two(&advent_of_code::template::read_file("examples", DAY));
         assert_eq!(result, Some(51));
     }
 }
-
-// Part 1: 8034 (23.6ms)
-// Part 2: Part 1: 8034 (6.9ms) && Part 2: 8225 (345.4ms)
@@ -1,225 +1,148 @@
 advent_of_code::solution!(17);
 
-use std::cmp::{Ordering, Reverse};
-use std::collections::HashMap;
-use std::collections::{BinaryHeap, HashSet};
-use std::u32;
+use advent_of_code::utils::map::Direction;
 
-use advent_of_code::utils::map::*;
-use strum::IntoEnumIterator;
+/// Directions in the order of their index, see `usize::from(Direction)`.
+const DIRECTIONS: [Direction; 4] = [
+    Direction::Up,
+    Direction::Down,
+    Direction::Left,
+    Direction::Right,
+];
+
+/// Heat loss of a single block is at most 9, so costs in the queue never span more buckets than this.
+const BUCKETS: usize = 16;
 
 #[derive(Debug)]
 struct Maze {
-    map: HashMap<Position, u8>,
-    ending_point: Position,
+    width: usize,
+    height: usize,
+    heat: Vec<u8>,
 }
 
-#[derive(Clone, Eq, PartialEq, Debug)]
-struct ExtendedNode {
-    heat: u32,
-    position: Position,
-    counter: u8,
-    direction: Option<Direction>,
+/// Priority queue for small integer costs: the entries of the current cost are popped before moving on.
+/// All queued costs lie within `BUCKETS` of the current one, so the buckets can be reused in a ring.
+struct BucketQueue {
+    buckets: Vec<Vec<usize>>,
+    cost: u32,
+    len: usize,
 }
 
-type VisitedKey = (Position, u8, Option<Direction>);
-type ConstraintFunction = dyn Fn(&ExtendedNode, &Direction) -> bool;
+impl BucketQueue {
+    fn new() -> Self {
+        Self {
+            buckets: vec![vec![]; BUCKETS],
+            cost: 0,
+            len: 0,
+        }
+    }
 
-impl Ord for ExtendedNode {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.heat.cmp(&self.heat).then_with(|| {
-            let a = self.position.0 + self.position.1;
-            let b = other.position.0 + other.position.1;
-            a.cmp(&b)
-        })
+    fn push(&mut self, cost: u32, state: usize) {
+        self.buckets[cost as usize % BUCKETS].push(state);
+        self.len += 1;
     }
-}
 
-impl PartialOrd for ExtendedNode {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    fn pop(&mut self) -> Option<(u32, usize)> {
+        if self.len == 0 {
+            return None;
+        }
+        loop {
+            if let Some(state) = self.buckets[self.cost as usize % BUCKETS].pop() {
+                self.len -= 1;
+                return Some((self.cost, state));
+            }
+            self.cost += 1;
+        }
     }
 }
 
 impl Maze {
     fn new(input: &str) -> Self {
-        let mut map: HashMap<Position, u8> = HashMap::new();
-
-        input.lines().enumerate().for_each(|(y, l)| {
-            l.chars().enumerate().for_each(|(x, c)| {
-                map.insert((x, y), c.to_digit(10).unwrap() as u8);
-            });
-        });
-
-        let end_y = input.lines().count();
-        let end_x = input.lines().next().unwrap().len();
-        let ending_point: Position = (end_x - 1, end_y - 1);
-
-        Self { map, ending_point }
+        let lines: Vec<&str> = input.lines().collect();
+        Self {
+            width: lines.first().map_or(0, |l| l.len()),
+            height: lines.len(),
+            heat: lines
+                .iter()
+                .flat_map(|l| l.bytes().map(|c| c - b'0'))
+                .collect(),
+        }
     }
 
-    pub fn get_value(&self, pos: &Position) -> u32 {
-        *self.map.get(pos).unwrap() as u32
+    fn step(&self, cell: usize, direction: Direction) -> Option<usize> {
+        let (x, y) = (cell % self.width, cell / self.width);
+        match direction {
+            Direction::Up => (y > 0).then(|| cell - self.width),
+            Direction::Down => (y + 1 < self.height).then(|| cell + self.width),
+            Direction::Left => (x > 0).then(|| cell - 1),
+            Direction::Right => (x + 1 < self.width).then(|| cell + 1),
+        }
     }
 
-    fn dijkstra_algorithm(&self, constraint: &ConstraintFunction) -> Option<u32> {
-        let mut visited_vertices: HashMap<VisitedKey, u32> = HashMap::new();
-        let mut unvisited_vertices: BinaryHeap<ExtendedNode> = BinaryHeap::new();
-        let mut unvisited_keys: HashSet<VisitedKey> = HashSet::new();
-
-        let node = ExtendedNode {
-            heat: 0,
-            position: (0, 0),
-            counter: 0,
-            direction: None,
-        };
-        unvisited_vertices.push(node);
-        visited_vertices.insert(((0, 0), 0, None), 0);
-
-        while let Some(node) = unvisited_vertices.pop() {
-            let key: VisitedKey = (node.position, node.counter, node.direction);
-            if node.heat > *visited_vertices.get(&key).unwrap_or(&u32::MAX) {
-                continue;
-            }
-
-            if node.position == self.ending_point {
-                return Some(node.heat);
-            }
-            visited_vertices.insert(key, node.heat);
-
-            let adjacent_nodes = self.get_adjacent_nodes(node.clone(), constraint);
-            for new_node in adjacent_nodes {
-                let new_key = (new_node.position, new_node.counter, new_node.direction);
-                if unvisited_keys.contains(&new_key) {
-                    continue;
-                }
-
-                if !visited_vertices.contains_key(&new_key)
-                    || new_node.heat < visited_vertices[&new_key]
-                {
-                    unvisited_vertices.push(new_node.clone());
-                    unvisited_keys.insert(new_key);
-                }
-            }
+    /// Least heat loss from the top-left to the bottom-right block for a crucible that moves
+    /// at least `min_straight` and at most `max_straight` blocks before turning, and before stopping.
+    ///
+    /// Dijkstra on the states (cell, direction, run length), stored in a dense array.
+    fn minimum_heat_loss(&self, min_straight: usize, max_straight: usize) -> Option<u32> {
+        let end = self.heat.len().checked_sub(1)?;
+        if end == 0 {
+            return Some(0);
         }
 
-        Some(self.get_result(&visited_vertices))
-    }
+        let runs = max_straight + 1;
+        let index = |cell: usize, direction: usize, run: usize| (cell * 4 + direction) * runs + run;
+        let mut best = vec![u32::MAX; self.heat.len() * 4 * runs];
+        let mut queue = BucketQueue::new();
 
-    fn get_adjacent_nodes(
-        &self,
-        cur_node: ExtendedNode,
-        constraint: &ConstraintFunction,
-    ) -> Vec<ExtendedNode> {
-        let directions = self.get_next_steps(&cur_node, constraint);
-        let mut result = vec![];
-        for d in directions {
-            let next_position = d.get_next_pos(&cur_node.position);
-
-            let adj_node = ExtendedNode {
-                heat: cur_node.heat + self.get_value(&next_position),
-                position: next_position,
-                counter: get_new_counter(&cur_node.direction, &d, cur_node.counter),
-                direction: Some(d),
+        let relax = |best: &mut Vec<u32>, queue: &mut BucketQueue, cell, direction, run, cost| {
+            let Some(next) = self.step(cell, DIRECTIONS[direction]) else {
+                return;
             };
+            let state = index(next, direction, run);
+            let cost = cost + u32::from(self.heat[next]);
+            if cost < best[state] {
+                best[state] = cost;
+                queue.push(cost, state);
+            }
+        };
 
-            result.push(adj_node)
+        // the crucible starts without a direction, so both moves count as a turn.
+        for direction in [Direction::Right, Direction::Down] {
+            relax(&mut best, &mut queue, 0, usize::from(direction), 1, 0);
         }
-        result
-    }
 
-    fn get_next_steps(
-        &self,
-        cur_node: &ExtendedNode,
-        constraint: &ConstraintFunction,
-    ) -> Vec<Direction> {
-        let prev_direction = cur_node.direction;
-        let cur_pos = cur_node.position;
-        match prev_direction {
-            // Starting Point
-            None => vec![Direction::Down, Direction::Right],
-            Some(prev_direction) => {
-                let possible_directions = Direction::iter();
-
-                let res = possible_directions
-                    .filter(|d| {
-                        if *d == prev_direction.opposite() || constraint(cur_node, d) {
-                            return false;
-                        }
-
-                        match *d {
-                            Direction::Down => cur_pos.1 != self.ending_point.1,
-                            Direction::Up => cur_pos.1 != 0,
-                            Direction::Left => cur_pos.0 != 0,
-                            Direction::Right => cur_pos.0 != self.ending_point.0,
-                        }
-                    })
-                    .collect();
-                res
+        while let Some((cost, state)) = queue.pop() {
+            if cost > best[state] {
+                continue;
             }
-        }
-    }
-
-    fn get_result<'a>(&'a self, visited_vertices: &'a HashMap<VisitedKey, u32>) -> u32 {
-        let mut results: BinaryHeap<Reverse<&u32>> = BinaryHeap::new();
-        for i in 0..3 {
-            for direction in [Direction::Right, Direction::Down] {
-                let key = (self.ending_point, i, Some(direction));
-                results.push(Reverse(visited_vertices.get(&key).unwrap_or(&u32::MAX)))
+            let (cell, direction, run) = (state / runs / 4, state / runs % 4, state % runs);
+            if cell == end && run >= min_straight {
+                return Some(cost);
             }
-        }
-        match results.pop() {
-            Some(Reverse(result)) => *result,
-            None => panic!("He"),
-        }
-    }
-}
 
-fn get_new_counter(
-    prev_direction: &Option<Direction>,
-    current_direction: &Direction,
-    counter: u8,
-) -> u8 {
-    match *prev_direction {
-        Some(direction) => {
-            if *current_direction == direction {
-                counter + 1
-            } else {
-                0
+            for (next_direction, next) in DIRECTIONS.iter().enumerate() {
+                if *next == DIRECTIONS[direction].opposite() {
+                    continue;
+                }
+                if next_direction == direction {
+                    if run < max_straight {
+                        relax(&mut best, &mut queue, cell, direction, run + 1, cost);
+                    }
+                } else if run >= min_straight {
+                    relax(&mut best, &mut queue, cell, next_direction, 1, cost);
+                }
             }
         }
-        None => 0,
+        None
     }
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let maze: Maze = Maze::new(input);
-    let constraint = |node: &ExtendedNode, next_direction: &Direction| {
-        let counter = node.counter;
-        let prev_direction = node.direction.unwrap();
-        counter == 2 && *next_direction == prev_direction
-    };
-    maze.dijkstra_algorithm(&constraint)
+    Maze::new(input).minimum_heat_loss(1, 3)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let maze: Maze = Maze::new(input);
-    let ending_point = maze.ending_point;
-    let constraint = move |node: &ExtendedNode, next_direction: &Direction| {
-        let counter = node.counter;
-        let prev_direction = node.direction.unwrap();
-
-        if next_direction.get_next_pos(&node.position) == ending_point {
-            // <=, because we will do another move to land in the good spot
-            return counter <= 3 || *next_direction != prev_direction;
-        }
-
-        let not_enough_moves = counter < 3 && *next_direction != prev_direction;
-        let reached_maximum_moves = counter == 9 && *next_direction == prev_direction;
-
-        not_enough_moves || reached_maximum_moves
-    };
-    maze.dijkstra_algorithm(&constraint)
+    Maze::new(input).minimum_heat_loss(4, 10)
 }
 
 #[cfg(test)]
@@ -232,9 +155,19 @@ mod tests {
 3215
 3255
 3446";
-        let result = part_one(&maze_str);
+        let result = part_one(maze_str);
         assert_eq!(result, Some(21))
     }
+    #[test]
+    fn test_straight_limits() {
+        let maze = Maze::new("111\n191\n111");
+        assert_eq!(maze.minimum_heat_loss(1, 3), Some(4));
+        // turning after every block forces a detour through the middle.
+        assert_eq!(maze.minimum_heat_loss(1, 1), Some(12));
+        // the crucible can't stop after two blocks.
+        assert_eq!(maze.minimum_heat_loss(3, 3), None);
+    }
+
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
@@ -254,9 +187,7 @@ mod tests {
 999999999991
 999999999991
 999999999991";
-        let result = part_two(&maze_str);
+        let result = part_two(maze_str);
         assert_eq!(result, Some(71));
     }
 }
-
-// Part 1: 1001 (878.9ms)  Part 2: 1197 (2.5s)
@@ -110,7 +110,3 @@ mod tests {
         assert_eq!(result, Some(952408144115));
     }
 }
-
-// On Macbook Pro M4
-// Part 1: 48503 (507.5µs)
-// Part 2: 148442153147147 (860.5µs)
@@ -1,4 +1,6 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, check_examples, download, leaderboard, progress, read, scaffold, search, solve, time,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
@@ -26,14 +28,40 @@ mod args {
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            /// `Some` when fuzzing, with an optional number of iterations.
+            fuzz: Option<Option<usize>>,
+            watch: bool,
+            skip_examples: bool,
         },
         All {
             release: bool,
         },
+        CheckExamples {
+            release: bool,
+            update: bool,
+        },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            memory: bool,
+            perf: bool,
+            note: Option<String>,
+        },
+        History {
+            day: Day,
+        },
+        Progress {
+            store: bool,
+            tests: bool,
+        },
+        Search {
+            term: Option<String>,
+            tag: Option<String>,
+        },
+        Leaderboard {
+            id: String,
+            day: Option<Day>,
         },
         #[cfg(feature = "today")]
         Today,
@@ -46,14 +74,45 @@ mod args {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
             },
+            Some("check-examples") => AppArguments::CheckExamples {
+                release: args.contains("--release"),
+                update: args.contains("--update"),
+            },
+            Some("time") if args.contains("--history") => AppArguments::History {
+                day: args.free_from_str()?,
+            },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let memory = args.contains("--memory");
+                let perf = args.contains("--perf");
+                let note = args.opt_value_from_str("--note")?;
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    memory,
+                    perf,
+                    note,
+                }
+            }
+            Some("progress") => AppArguments::Progress {
+                store: args.contains("--store"),
+                tests: !args.contains("--no-tests"),
+            },
+            Some("search") => {
+                let tag = args.opt_value_from_str("--tag")?;
+                AppArguments::Search {
+                    term: args.opt_free_from_str()?,
+                    tag,
+                }
+            }
+            Some("leaderboard") => {
+                let day = args.opt_value_from_str("--day")?;
+                AppArguments::Leaderboard {
+                    id: args.free_from_str()?,
+                    day,
                 }
             }
             Some("download") => AppArguments::Download {
@@ -71,6 +130,13 @@ mod args {
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                fuzz: if args.contains("--fuzz") {
+                    Some(args.opt_value_from_str("--iterations")?)
+                } else {
+                    None
+                },
+                watch: args.contains("--watch"),
+                skip_examples: args.contains("--skip-examples"),
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -101,7 +167,21 @@ fn main() {
         }
         Ok(args) => match args {
             AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::CheckExamples { release, update } => {
+                check_examples::handle(release, update);
+            }
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                memory,
+                perf,
+                note,
+            } => time::handle(day, all, store, memory, perf, note),
+            AppArguments::History { day } => time::history(day),
+            AppArguments::Progress { store, tests } => progress::handle(store, tests),
+            AppArguments::Search { term, tag } => search::handle(term, tag),
+            AppArguments::Leaderboard { id, day } => leaderboard::handle(&id, day),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold { day, download } => {
@@ -115,7 +195,10 @@ fn main() {
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                fuzz,
+                watch,
+                skip_examples,
+            } => solve::handle(day, release, dhat, submit, fuzz, watch, skip_examples),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
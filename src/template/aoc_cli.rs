@@ -1,6 +1,7 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
     fmt::Display,
+    io::Write,
     process::{Command, Output, Stdio},
 };
 
@@ -11,6 +12,9 @@ pub enum AocCommandError {
     CommandNotFound,
     CommandNotCallable,
     BadExitStatus(Output),
+    SessionNotFound,
+    YearNotSet,
+    RequestFailed(String),
 }
 
 impl Display for AocCommandError {
@@ -21,6 +25,12 @@ impl Display for AocCommandError {
             AocCommandError::BadExitStatus(_) => {
                 write!(f, "aoc-cli exited with a non-zero status.")
             }
+            AocCommandError::SessionNotFound => write!(
+                f,
+                "no session cookie found in AOC_SESSION or ~/.adventofcode.session."
+            ),
+            AocCommandError::YearNotSet => write!(f, "AOC_YEAR is not set."),
+            AocCommandError::RequestFailed(url) => write!(f, "request to {url} failed."),
         }
     }
 }
@@ -77,7 +87,89 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+
+    // the response is captured to tell whether the answer was accepted.
+    let output = Command::new("aoc")
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(AocCommandError::BadExitStatus(output))
+    }
+}
+
+/// Whether adventofcode.com accepted a submitted answer.
+#[must_use]
+pub fn is_accepted(output: &Output) -> bool {
+    String::from_utf8_lossy(&output.stdout).contains("That's the right answer")
+}
+
+/* -------------------------------------------------------------------------- */
+
+// aoc-cli does not expose the leaderboard JSON, so it is requested via curl with the same session cookie.
+
+/// Base URL of the website, can be pointed at a local stand-in server via `AOC_BASE_URL`.
+fn get_base_url() -> String {
+    std::env::var("AOC_BASE_URL").map_or_else(
+        |_| "https://adventofcode.com".into(),
+        |url| url.trim_end_matches('/').to_string(),
+    )
+}
+
+/// Session cookie from `AOC_SESSION` or the file aoc-cli reads it from.
+fn get_session() -> Result<String, AocCommandError> {
+    if let Ok(session) = std::env::var("AOC_SESSION") {
+        return Ok(session.trim().to_string());
+    }
+
+    let home = std::env::var("HOME").map_err(|_| AocCommandError::SessionNotFound)?;
+    std::fs::read_to_string(std::path::Path::new(&home).join(".adventofcode.session"))
+        .map(|s| s.trim().to_string())
+        .map_err(|_| AocCommandError::SessionNotFound)
+}
+
+/// Fetches the JSON of a private leaderboard of the configured year.
+pub fn private_leaderboard(id: &str) -> Result<String, AocCommandError> {
+    let year = get_year().ok_or(AocCommandError::YearNotSet)?;
+    let session = get_session()?;
+    let url = format!(
+        "{}/{year}/leaderboard/private/view/{id}.json",
+        get_base_url()
+    );
+
+    // the cookie is passed as a config on stdin, arguments are visible to other users through `ps`.
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--location"])
+        .args(["--config", "-"])
+        .args(["--user-agent", "github.com/fspoettel/advent-of-code-rust"])
+        .arg(&url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        let session = session.replace('\\', "\\\\").replace('"', "\\\"");
+        writeln!(stdin, "cookie = \"session={session}\"")
+            .map_err(|_| AocCommandError::CommandNotCallable)?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        Err(AocCommandError::RequestFailed(url))
+    }
 }
 
 fn get_input_path(day: Day) -> String {
@@ -88,7 +180,7 @@ fn get_puzzle_path(day: Day) -> String {
     format!("data/puzzles/{day}.md")
 }
 
-fn get_year() -> Option<u16> {
+pub fn get_year() -> Option<u16> {
     match std::env::var("AOC_YEAR") {
         Ok(x) => x.parse().ok().or(None),
         Err(_) => None,
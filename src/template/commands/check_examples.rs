@@ -0,0 +1,174 @@
+use std::path::Path;
+use std::process::{self, Command};
+use std::time::{Duration, Instant};
+
+use crate::template::examples::{parse_answers, Status};
+use crate::template::meta::Meta;
+use crate::template::{all_days, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+pub struct DayResult {
+    pub day: Day,
+    pub parts: [Status; 2],
+    pub elapsed: Duration,
+    /// First lines of stderr if the solution did not run successfully.
+    pub error: Option<String>,
+}
+
+pub fn check_day(day: Day, is_release: bool) -> DayResult {
+    let day_padded = day.to_string();
+    let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    if is_release {
+        args.push("--release");
+    }
+    args.extend(["--", "--example"]);
+
+    let timer = Instant::now();
+    let output = Command::new("cargo").args(&args).output();
+    let elapsed = timer.elapsed();
+
+    let Ok(output) = output else {
+        return DayResult {
+            day,
+            parts: [Status::Error, Status::Error],
+            elapsed,
+            error: Some("could not invoke cargo".into()),
+        };
+    };
+
+    let answers = parse_answers(&String::from_utf8_lossy(&output.stdout));
+    let expected = Meta::read_or_default(day).example;
+    let is_success = output.status.success();
+
+    let parts = [1, 2].map(|part| {
+        let actual = answers[usize::from(part) - 1].as_deref();
+        if actual.is_none() && !is_success {
+            Status::Error
+        } else {
+            Status::new(expected.get(part), actual)
+        }
+    });
+
+    let error = if is_success {
+        None
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Some(
+            stderr
+                .lines()
+                .filter(|l| !l.trim().is_empty())
+                .take(3)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    };
+
+    DayResult {
+        day,
+        parts,
+        elapsed,
+        error,
+    }
+}
+
+/// Records the answers of parts without an expected answer in the day's metadata.
+fn store_missing(result: &DayResult) {
+    let update = Meta::update(result.day, |meta| {
+        for (part, status) in [1, 2].into_iter().zip(&result.parts) {
+            if let Status::Missing { actual } = status {
+                meta.example.set(part, Some(actual.clone()));
+            }
+        }
+    });
+
+    if let Err(e) = update {
+        eprintln!("Failed to store example answers of day {}: {e}", result.day);
+    }
+}
+
+pub fn handle(is_release: bool, update: bool) {
+    let days: Vec<Day> = all_days()
+        .filter(|day| Path::new(&format!("src/bin/{day}.rs")).exists())
+        .collect();
+
+    println!(
+        "Checking {} solutions against their examples...\n",
+        days.len()
+    );
+
+    println!(
+        "{ANSI_BOLD}{:<5} {:<12} {:<12} {:>10}{ANSI_RESET}",
+        "Day", "Part 1", "Part 2", "Time"
+    );
+
+    let mut results = vec![];
+    for day in days {
+        let result = check_day(day, is_release);
+        println!(
+            "{:<5} {:<12} {:<12} {:>10}",
+            result.day.to_string(),
+            result.parts[0].symbol(),
+            result.parts[1].symbol(),
+            format!("{:.1?}", result.elapsed)
+        );
+        results.push(result);
+    }
+
+    let mut failed = 0;
+    let mut missing = 0;
+    let mut stored = 0;
+    let mut passed = 0;
+
+    for result in &results {
+        for (i, status) in result.parts.iter().enumerate() {
+            match status {
+                Status::Pass => passed += 1,
+                Status::Fail { expected, actual } => {
+                    failed += 1;
+                    println!(
+                        "\n{ANSI_BOLD}Day {} part {}:{ANSI_RESET} expected {expected}, got {actual}",
+                        result.day,
+                        i + 1
+                    );
+                }
+                Status::Missing { actual } if update => {
+                    stored += 1;
+                    println!(
+                        "\n{ANSI_BOLD}Day {} part {}:{ANSI_RESET} got {actual}, stored as expected answer.",
+                        result.day,
+                        i + 1
+                    );
+                }
+                Status::Missing { actual } => {
+                    missing += 1;
+                    println!(
+                        "\n{ANSI_BOLD}Day {} part {}:{ANSI_RESET} got {actual}, no expected answer in data/meta/{}.json",
+                        result.day,
+                        i + 1,
+                        result.day
+                    );
+                }
+                Status::Error | Status::Unsolved => {}
+            }
+        }
+
+        if update {
+            store_missing(result);
+        }
+
+        if let Some(error) = &result.error {
+            failed += 1;
+            println!("\n{ANSI_BOLD}Day {} failed to run:{ANSI_RESET}", result.day);
+            println!("{ANSI_ITALIC}{error}{ANSI_RESET}");
+        }
+    }
+
+    if update {
+        println!("\n{passed} passed, {failed} failed, {stored} stored.");
+    } else {
+        println!("\n{passed} passed, {failed} failed, {missing} missing.");
+    }
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::template::{aoc_cli, Day};
+use crate::template::{aoc_cli, meta, Day};
 use std::process;
 
 pub fn handle(day: Day) {
@@ -11,4 +11,8 @@ pub fn handle(day: Day) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
+
+    if let Err(e) = meta::sync_from_puzzle(day) {
+        eprintln!("failed to update metadata: {e}");
+    }
 }
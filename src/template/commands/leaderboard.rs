@@ -0,0 +1,38 @@
+use std::process;
+
+use crate::template::leaderboard::{self, latest_day};
+use crate::template::Day;
+
+pub fn handle(id: &str, day: Option<Day>) {
+    let (leaderboard, age) = match leaderboard::load(id) {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("Failed to load leaderboard: {e}");
+            process::exit(1);
+        }
+    };
+
+    match age {
+        Some(age) => println!(
+            "Private leaderboard {id} ({}), cached {} min ago, refreshes in {} min.\n",
+            leaderboard.year,
+            age.as_secs() / 60,
+            leaderboard::minutes_until_refresh(age)
+        ),
+        None => println!("Private leaderboard {id} ({}).\n", leaderboard.year),
+    }
+
+    for line in leaderboard::render_ranking(&leaderboard) {
+        println!("{line}");
+    }
+
+    let day = day
+        .map(Day::into_inner)
+        .or_else(|| latest_day(&leaderboard));
+    if let Some(day) = day {
+        println!("\nDay {day}");
+        for line in leaderboard::render_day(&leaderboard, day) {
+            println!("{line}");
+        }
+    }
+}
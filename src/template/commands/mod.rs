@@ -1,6 +1,10 @@
 pub mod all;
+pub mod check_examples;
 pub mod download;
+pub mod leaderboard;
+pub mod progress;
 pub mod read;
 pub mod scaffold;
+pub mod search;
 pub mod solve;
 pub mod time;
@@ -0,0 +1,25 @@
+use crate::template::progress;
+
+pub fn handle(store: bool, tests: bool) {
+    if tests {
+        println!("Running tests of all solutions...\n");
+    }
+
+    let progress = progress::collect(tests);
+
+    for line in progress::render_calendar(&progress) {
+        println!("{line}");
+    }
+
+    if store {
+        println!();
+        match progress::update(&progress) {
+            Ok(()) => {
+                println!("Stored updated progress.");
+            }
+            Err(e) => {
+                eprintln!("Failed to store updated progress: {e}");
+            }
+        }
+    }
+}
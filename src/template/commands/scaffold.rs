@@ -4,6 +4,7 @@ use std::{
     process,
 };
 
+use crate::template::meta::{self, Meta};
 use crate::template::Day;
 
 const MODULE_TEMPLATE: &str =
@@ -14,7 +15,11 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(path)
 }
 
 pub fn handle(day: Day) {
@@ -64,6 +69,21 @@ pub fn handle(day: Day) {
         }
     }
 
+    let meta_path = meta::get_path(day);
+    if meta_path.exists() {
+        println!("Kept existing metadata file \"{}\"", meta_path.display());
+    } else {
+        match Meta::default().store(day) {
+            Ok(()) => {
+                println!("Created metadata file \"{}\"", meta_path.display());
+            }
+            Err(e) => {
+                eprintln!("Failed to create metadata file: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
     println!("---");
     println!("🎄 Type `cargo solve {day}` to run your solution.");
 }
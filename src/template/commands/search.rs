@@ -0,0 +1,57 @@
+use crate::template::search::{self, Query};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Matching lines shown per file.
+const MAX_LINES: usize = 3;
+/// Matching lines are cut off after this many characters.
+const MAX_WIDTH: usize = 100;
+
+fn truncate(line: &str) -> String {
+    if line.chars().count() > MAX_WIDTH {
+        format!("{}…", line.chars().take(MAX_WIDTH).collect::<String>())
+    } else {
+        line.to_string()
+    }
+}
+
+pub fn handle(term: Option<String>, tag: Option<String>) {
+    let matches = search::search(&Query { term, tag });
+
+    if matches.is_empty() {
+        println!("No days match.");
+        return;
+    }
+
+    for m in &matches {
+        let title = m
+            .title
+            .as_deref()
+            .map_or_else(String::new, |t| format!(": {t}"));
+        let tags = if m.tags.is_empty() {
+            String::new()
+        } else {
+            format!(" {ANSI_ITALIC}[{}]{ANSI_RESET}", m.tags.join(", "))
+        };
+        println!("{ANSI_BOLD}Day {}{title}{ANSI_RESET}{tags}", m.day);
+
+        for file in &m.files {
+            for line in file.lines.iter().take(MAX_LINES) {
+                println!(
+                    "  {}:{}  {}",
+                    file.path.display(),
+                    line.line_number,
+                    truncate(&line.line)
+                );
+            }
+            if file.lines.len() > MAX_LINES {
+                println!(
+                    "  {ANSI_ITALIC}… {} more in {}{ANSI_RESET}",
+                    file.lines.len() - MAX_LINES,
+                    file.path.display()
+                );
+            }
+        }
+    }
+
+    println!("\n{} day(s) match.", matches.len());
+}
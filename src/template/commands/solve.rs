@@ -1,8 +1,279 @@
-use std::process::{Command, Stdio};
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::process::{self, Command, Output, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
 
-use crate::template::Day;
+use crate::template::aoc_cli;
+use crate::template::commands::check_examples::check_day;
+use crate::template::examples::{parse_answers, Status};
+use crate::template::meta::Meta;
+use crate::template::watch::{self, AnswerChange, TestOutcome};
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+/// Editors often write a file in several steps, wait for them to finish before re-running.
+const SETTLE_TIME: Duration = Duration::from_millis(100);
+
+fn run_cargo(command: &str, day: Day, release: bool, args: &[&str]) -> Option<Output> {
+    let day_padded = day.to_string();
+    let mut cmd_args = vec![command, "--quiet", "--bin", &day_padded];
+    if release {
+        cmd_args.push("--release");
+    }
+    cmd_args.push("--");
+    cmd_args.extend(args);
+
+    Command::new("cargo").args(&cmd_args).output().ok()
+}
+
+/// Runs the tests and both parts of a day once. Returns the answers, or `None` if the solution did not compile.
+fn run_watched(
+    day: Day,
+    release: bool,
+    previous: Option<&[Option<String>; 2]>,
+) -> Option<[Option<String>; 2]> {
+    let timer = Instant::now();
+
+    let Some(output) = run_cargo("test", day, release, &[]) else {
+        eprintln!("Could not invoke cargo.");
+        return None;
+    };
+
+    match watch::parse_test_output(
+        &String::from_utf8_lossy(&output.stdout),
+        &String::from_utf8_lossy(&output.stderr),
+    ) {
+        TestOutcome::CompileError(error) => {
+            println!("{ANSI_BOLD}‼ Compile error{ANSI_RESET}");
+            println!("{ANSI_ITALIC}{error}{ANSI_RESET}");
+            return None;
+        }
+        TestOutcome::Finished { passed, failed } if failed.is_empty() => {
+            println!("Tests: ✔ {passed} passed");
+        }
+        TestOutcome::Finished { passed, failed } => {
+            println!(
+                "Tests: ✖ {passed} passed, {} failed ({})",
+                failed.len(),
+                failed.join(", ")
+            );
+        }
+    }
+
+    // fall back to the examples until the input was downloaded.
+    let has_input = Path::new(&format!("data/inputs/{day}.txt")).exists();
+    let args: &[&str] = if has_input { &[] } else { &["--example"] };
+    let output = run_cargo("run", day, release, args)?;
+    let answers = parse_answers(&String::from_utf8_lossy(&output.stdout));
+
+    for (i, answer) in answers.iter().enumerate() {
+        let change = match AnswerChange::new(previous.map(|p| &p[i]), answer) {
+            AnswerChange::First => String::new(),
+            AnswerChange::Unchanged => " (unchanged)".into(),
+            AnswerChange::Changed { previous } => format!(
+                " {ANSI_BOLD}(changed, was {}){ANSI_RESET}",
+                previous.as_deref().unwrap_or("✖")
+            ),
+        };
+        println!(
+            "Part {}: {ANSI_BOLD}{}{ANSI_RESET}{change}",
+            i + 1,
+            answer.as_deref().unwrap_or("✖")
+        );
+    }
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let error: Vec<&str> = stderr.lines().take(3).collect();
+        println!("{ANSI_BOLD}‼ Solution failed{ANSI_RESET}");
+        println!("{ANSI_ITALIC}{}{ANSI_RESET}", error.join("\n"));
+    }
+
+    println!(
+        "{ANSI_ITALIC}{} in {:.1?}{ANSI_RESET}",
+        if has_input { "input" } else { "example" },
+        timer.elapsed()
+    );
+
+    Some(answers)
+}
+
+/// Re-runs tests and both parts whenever the day's solution, `src/utils` or its inputs change.
+fn watch(day: Day, release: bool) {
+    println!(
+        "Watching {} files of day {day}, press Ctrl+C to stop.\n",
+        watch::watched_files(day).len()
+    );
+
+    let mut snapshot = watch::snapshot(day);
+    let mut answers = run_watched(day, release, None);
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        if watch::changed_files(&snapshot, &watch::snapshot(day)).is_empty() {
+            continue;
+        }
+
+        thread::sleep(SETTLE_TIME);
+        let current = watch::snapshot(day);
+        let changed = watch::changed_files(&snapshot, &current);
+        snapshot = current;
+
+        let changed: Vec<String> = changed.iter().map(|p| p.display().to_string()).collect();
+        println!(
+            "\n{ANSI_BOLD}── {} changed ──{ANSI_RESET}",
+            changed.join(", ")
+        );
+
+        if let Some(current) = run_watched(day, release, answers.as_ref()) {
+            answers = Some(current);
+        }
+    }
+}
+
+/// Runs the solution on its examples. Returns whether the real input may be run:
+/// no part gave a wrong answer or failed. Parts without an expected answer don't block.
+fn check_examples(day: Day, release: bool) -> bool {
+    println!("Checking examples...");
+    let result = check_day(day, release);
+
+    let summary: Vec<String> = result
+        .parts
+        .iter()
+        .enumerate()
+        .map(|(i, status)| format!("part {} {}", i + 1, status.symbol()))
+        .collect();
+    println!("Examples: {}", summary.join(", "));
+
+    for (i, status) in result.parts.iter().enumerate() {
+        if let Status::Fail { expected, actual } = status {
+            println!(
+                "{ANSI_BOLD}Part {}:{ANSI_RESET} expected {expected}, got {actual}",
+                i + 1
+            );
+        }
+    }
+
+    if let Some(error) = &result.error {
+        println!("{ANSI_ITALIC}{error}{ANSI_RESET}");
+    }
+
+    // only parts without an expected answer may go unchecked.
+    let passed = result
+        .parts
+        .iter()
+        .all(|s| matches!(s, Status::Pass | Status::Missing { .. } | Status::Unsolved));
+
+    if passed {
+        println!();
+    } else {
+        eprintln!("\nNot running the real input until the examples pass. Append `--skip-examples` to run it anyway.");
+    }
+
+    passed
+}
+
+/// Runs cargo, forwarding its output while capturing stdout.
+fn run_forwarded(args: &[String]) -> String {
+    let mut cmd = Command::new("cargo")
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    let mut output = vec![];
+    if let Some(mut child_stdout) = cmd.stdout.take() {
+        let mut stdout = io::stdout();
+        let mut buffer = [0; 1024];
+        // forward chunks instead of lines, the runner overwrites progress output without a newline.
+        while let Ok(n @ 1..) = child_stdout.read(&mut buffer) {
+            let _ = stdout.write_all(&buffer[..n]);
+            let _ = stdout.flush();
+            output.extend_from_slice(&buffer[..n]);
+        }
+    }
+
+    cmd.wait().unwrap();
+    String::from_utf8_lossy(&output).to_string()
+}
+
+fn confirm(question: &str) -> bool {
+    print!("{question} [y/N] ");
+    let _ = io::stdout().flush();
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer).is_ok() && answer.trim().eq_ignore_ascii_case("y")
+}
+
+/// Submits the answer of a part. Asks first if it differs from the answer accepted before,
+/// and records the answer in the day's metadata once it is accepted.
+fn submit(day: Day, part: u8, output: &str) {
+    let Some(answer) = parse_answers(output)[usize::from(part) - 1].clone() else {
+        eprintln!("Part {part} has no answer to submit.");
+        return;
+    };
+
+    match Meta::read_or_default(day).answers.get(part) {
+        Some(accepted) if accepted == answer => {
+            println!("Part {part} was already accepted with {answer}, not submitting.");
+            return;
+        }
+        Some(accepted) => {
+            let question = format!(
+                "The answer of part {part} changed from {accepted} to {answer}. Submit it?"
+            );
+            if !confirm(&question) {
+                println!("Not submitting.");
+                return;
+            }
+        }
+        None => {}
+    }
+
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        process::exit(1);
+    }
+
+    println!("Submitting result via aoc-cli...");
+    match aoc_cli::submit(day, part, &answer) {
+        Ok(output) if aoc_cli::is_accepted(&output) => {
+            if let Err(e) = Meta::update(day, |meta| meta.answers.set(part, Some(answer))) {
+                eprintln!("Failed to record the accepted answer: {e}");
+            }
+        }
+        Ok(_) => {}
+        Err(e) => eprintln!("failed to call aoc-cli: {e}"),
+    }
+}
+
+#[allow(clippy::fn_params_excessive_bools)]
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    fuzz: Option<Option<usize>>,
+    watch: bool,
+    skip_examples: bool,
+) {
+    if let Some(part) = submit_part.filter(|p| !(1..=2).contains(p)) {
+        eprintln!("Cannot submit part {part}, a day only has parts 1 and 2.");
+        process::exit(1);
+    }
+
+    if watch {
+        self::watch(day, release);
+        return;
+    }
+
+    // fuzzing and heap profiling don't care about the answers.
+    if fuzz.is_none() && !dhat && !skip_examples && !check_examples(day, release) {
+        process::exit(1);
+    }
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -18,17 +289,17 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
+    if let Some(iterations) = fuzz {
+        cmd_args.push("--fuzz".to_string());
+        if let Some(iterations) = iterations {
+            cmd_args.push("--iterations".to_string());
+            cmd_args.push(iterations.to_string());
+        }
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+    let output = run_forwarded(&cmd_args);
 
-    cmd.wait().unwrap();
+    if let Some(part) = submit_part {
+        submit(day, part, &output);
+    }
 }
@@ -1,27 +1,89 @@
 use std::collections::HashSet;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::template::history;
+use crate::template::machine::Machine;
+use crate::template::meta::{self, Meta};
 use crate::template::run_multi::run_multi;
 use crate::template::timings::Timings;
 use crate::template::{all_days, readme_benchmarks, Day};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
+/// Appends a benchmark note to the metadata of every timed day, e.g. to document what an optimization achieved.
+fn store_note(timings: &Timings, note: &str) {
+    for timing in &timings.data {
+        let date = timing
+            .timestamp
+            .map_or_else(|| "unknown date".into(), history::format_timestamp);
+        let commit = timing
+            .machine
+            .as_ref()
+            .and_then(|m| m.commit.clone())
+            .map_or_else(String::new, |c| format!(" ({c})"));
+
+        let entry = format!(
+            "{date}{commit}: pt1 {} & pt2 {}: {note}",
+            timing.part_1.as_deref().unwrap_or("-"),
+            timing.part_2.as_deref().unwrap_or("-"),
+        );
+
+        match Meta::update(timing.day, |meta| meta.notes.push(entry)) {
+            Ok(()) => println!("Added note to {}.", meta::get_path(timing.day).display()),
+            Err(e) => eprintln!("Failed to add note to day {}: {e}", timing.day),
+        }
+    }
+}
+
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    memory: bool,
+    perf: bool,
+    note: Option<String>,
+) {
+    let machine = Machine::detect("release");
+    println!("Benchmarking on {}.\n", machine.describe());
+
+    let perf = if perf && !cfg!(target_os = "linux") {
+        eprintln!("Hardware counters are only supported on linux, ignoring `--perf`.");
+        false
+    } else {
+        perf
+    };
+
     let stored_timings = Timings::read_from_file();
+    let machine_timings = stored_timings.for_machine(Some(&machine.id()));
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
                 all_days().collect()
             } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
+                // when the `--all` flag is not set, filter out days that are fully benched on this machine.
                 all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                    .filter(|day| !machine_timings.is_day_complete(*day))
                     .collect()
             }
         },
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let mut timings = run_multi(&days_to_run, true, true, memory, perf).unwrap();
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .ok();
+
+    for timing in &mut timings.data {
+        timing.machine = Some(machine.clone());
+        timing.timestamp = timestamp;
+    }
+
+    if let Some(note) = note {
+        println!();
+        store_note(&timings, &note);
+    }
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
@@ -32,9 +94,17 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+            Err(e) => {
+                eprintln!("Failed to store updated benchmarks: {e}");
             }
         }
     }
 }
+
+/// Print how the stored timings of a day progressed over time.
+pub fn history(day: Day) {
+    let timings = Timings::read_from_file();
+    for line in history::report(day, &timings) {
+        println!("{line}");
+    }
+}
@@ -0,0 +1,138 @@
+/// Module that checks solutions against the examples in `data/examples`.
+/// Expected answers are read from the `example` field of the day's metadata in `data/meta`.
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+/// Extracts the answers from the output of a solution bin, e.g. `Part 1: 142 (1.2ms)`.
+pub fn parse_answers(output: &str) -> [Option<String>; 2] {
+    let mut answers = [None, None];
+    let lines: Vec<&str> = output
+        .lines()
+        // intermediate output is overwritten with a carriage return.
+        .map(|l| l.rsplit('\r').next().unwrap_or(l))
+        .collect();
+
+    for (i, line) in lines.iter().enumerate() {
+        let Some(rest) = line.strip_prefix("Part ") else {
+            continue;
+        };
+        let Some((part, result)) = rest.split_once(": ") else {
+            continue;
+        };
+        let index = match part {
+            "1" => 0,
+            "2" => 1,
+            _ => continue,
+        };
+
+        answers[index] = if result.starts_with('▼') {
+            // multi-line results are printed below the part line.
+            let result: Vec<&str> = lines[i + 1..]
+                .iter()
+                .take_while(|l| !l.starts_with("Part "))
+                .copied()
+                .collect();
+            Some(result.join("\n").trim_end().to_string())
+        } else if result.starts_with('✖') {
+            None
+        } else {
+            result
+                .strip_prefix(ANSI_BOLD)
+                .and_then(|r| r.split_once(ANSI_RESET))
+                .map(|(answer, _)| answer.to_string())
+        };
+    }
+
+    answers
+}
+
+/// Outcome of checking one part against its example.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    /// The part returned an answer, but there is no expected answer to compare it to.
+    Missing {
+        actual: String,
+    },
+    /// The part did not return an answer, and there is no expected answer either.
+    Unsolved,
+    /// The solution failed to compile or panicked.
+    Error,
+}
+
+impl Status {
+    pub fn new(expected: Option<&str>, actual: Option<&str>) -> Self {
+        match (expected, actual) {
+            (None, None) => Status::Unsolved,
+            // a missing answer is shown like the runner shows it.
+            (Some(expected), None) => Status::Fail {
+                expected: expected.into(),
+                actual: "✖".into(),
+            },
+            (None, Some(actual)) => Status::Missing {
+                actual: actual.into(),
+            },
+            (Some(expected), Some(actual)) if expected == actual => Status::Pass,
+            (Some(expected), Some(actual)) => Status::Fail {
+                expected: expected.into(),
+                actual: actual.into(),
+            },
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Status::Pass => "✔ pass",
+            Status::Fail { .. } => "✖ fail",
+            Status::Missing { .. } => "? missing",
+            Status::Unsolved => "· unsolved",
+            Status::Error => "‼ error",
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_answers, Status};
+
+    #[test]
+    fn parses_answers_from_output() {
+        let output = "Part 1: \x1b[1m142\x1b[0m\rPart 1: \x1b[1m142\x1b[0m (1.2ms)\nPart 2: ✖             \n";
+        assert_eq!(parse_answers(output), [Some("142".into()), None]);
+
+        let output = "Part 1: ▼ (1.0ms)\n#..\n.#.\nPart 2: \x1b[1m7\x1b[0m (1.0ms)\n";
+        assert_eq!(
+            parse_answers(output),
+            [Some("#..\n.#.".into()), Some("7".into())]
+        );
+    }
+
+    #[test]
+    fn compares_answers() {
+        assert_eq!(Status::new(Some("1"), Some("1")), Status::Pass);
+        assert_eq!(
+            Status::new(Some("1"), Some("2")),
+            Status::Fail {
+                expected: "1".into(),
+                actual: "2".into()
+            }
+        );
+        assert_eq!(
+            Status::new(None, Some("2")),
+            Status::Missing { actual: "2".into() }
+        );
+        assert_eq!(
+            Status::new(Some("1"), None),
+            Status::Fail {
+                expected: "1".into(),
+                actual: "✖".into()
+            }
+        );
+        assert_eq!(Status::new(None, None), Status::Unsolved);
+    }
+}
@@ -0,0 +1,321 @@
+/// Feeds randomly mutated puzzle inputs to a solution to find inputs that make it panic or hang.
+/// The mutator starts from the example files of a day. Crashing inputs are saved to `data/fuzz/NN/`.
+use std::collections::HashSet;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::{env, fs};
+
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::utils::testing::Rng;
+
+/// A solution part, taking the (possibly malformed) puzzle input.
+pub type FuzzTarget = (u8, fn(&str));
+
+/// Inputs taking longer than this are considered hanging.
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Input currently being run. If the process aborts, e.g. on a stack overflow, it is left behind and reported on the next run.
+const CURRENT_INPUT: &str = "current-input.txt";
+
+fn get_fuzz_dir(day: Day) -> PathBuf {
+    Path::new("data").join("fuzz").join(day.to_string())
+}
+
+/// Seed inputs of the mutator: the example files of a day, e.g. `01.txt` and `01-2.txt`.
+fn read_corpus(day: Day) -> Vec<Vec<u8>> {
+    let mut corpus: Vec<Vec<u8>> = fs::read_dir(Path::new("data").join("examples"))
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter(|e| {
+                    let name = e.file_name().to_string_lossy().to_string();
+                    name.starts_with(&day.to_string()) && name.ends_with(".txt")
+                })
+                .filter_map(|e| fs::read(e.path()).ok())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if corpus.is_empty() {
+        corpus.push(vec![]);
+    }
+
+    corpus
+}
+
+/// Bytes that tend to be meaningful in puzzle inputs.
+static INTERESTING_BYTES: &[u8] = b"0123456789\n ,:-#.?|/\\SLRUDA";
+
+/// Numbers that tend to break parsers and arithmetic.
+static INTERESTING_NUMBERS: &[&str] = &[
+    "0",
+    "-1",
+    "255",
+    "65536",
+    "2147483648",
+    "18446744073709551616",
+    "99999999999999999999999",
+];
+
+fn mutate(rng: &mut Rng, input: &mut Vec<u8>, max_len: usize) {
+    let random_index = |rng: &mut Rng, input: &Vec<u8>| {
+        if input.is_empty() {
+            0
+        } else {
+            rng.index(input.len())
+        }
+    };
+
+    match rng.index(8) {
+        // replace a byte
+        0 if !input.is_empty() => {
+            let i = random_index(rng, input);
+            input[i] = *rng.choose(INTERESTING_BYTES);
+        }
+        // insert a byte
+        1 => {
+            let i = random_index(rng, input);
+            let byte = if rng.bool() {
+                *rng.choose(INTERESTING_BYTES)
+            } else {
+                (rng.next_u64() & 0xff) as u8
+            };
+            input.insert(i, byte);
+        }
+        // delete a range
+        2 if !input.is_empty() => {
+            let start = random_index(rng, input);
+            let end = (start + rng.index(16) + 1).min(input.len());
+            input.drain(start..end);
+        }
+        // duplicate a range
+        3 if !input.is_empty() => {
+            let start = random_index(rng, input);
+            let end = (start + rng.index(64) + 1).min(input.len());
+            let chunk = input[start..end].to_vec();
+            let at = random_index(rng, input);
+            input.splice(at..at, chunk);
+        }
+        // truncate
+        4 if !input.is_empty() => {
+            let len = random_index(rng, input);
+            input.truncate(len);
+        }
+        // replace a number
+        5 => {
+            let digits: Vec<usize> = (0..input.len())
+                .filter(|i| input[*i].is_ascii_digit())
+                .collect();
+            if digits.is_empty() {
+                return;
+            }
+            let start = *rng.choose(&digits);
+            let end = (start..input.len())
+                .find(|i| !input[*i].is_ascii_digit())
+                .unwrap_or(input.len());
+            let number = rng.choose(INTERESTING_NUMBERS).as_bytes().to_vec();
+            input.splice(start..end, number);
+        }
+        // swap two lines
+        6 => {
+            let mut lines: Vec<Vec<u8>> =
+                input.split(|c| *c == b'\n').map(<[u8]>::to_vec).collect();
+            if lines.len() < 2 {
+                return;
+            }
+            let a = rng.index(lines.len());
+            let b = rng.index(lines.len());
+            lines.swap(a, b);
+            *input = lines.join(&b'\n');
+        }
+        // remove or add a trailing newline
+        _ => {
+            if input.last() == Some(&b'\n') {
+                input.pop();
+            } else {
+                input.push(b'\n');
+            }
+        }
+    }
+
+    input.truncate(max_len);
+}
+
+fn hash(input: &[u8]) -> u64 {
+    // FNV-1a
+    input.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn save_input(day: Day, name: &str, input: &[u8]) -> PathBuf {
+    let dir = get_fuzz_dir(day);
+    let path = dir.join(format!("{name}-{:016x}.txt", hash(input)));
+    if let Err(e) = fs::create_dir_all(&dir).and_then(|()| fs::write(&path, input)) {
+        eprintln!("Failed to save input to {}: {e}", path.display());
+    }
+    path
+}
+
+enum Outcome {
+    Ok,
+    Panic(String),
+    Timeout,
+}
+
+/// Runs a part on a separate thread, so that hanging inputs can be detected.
+fn run_case(target: fn(&str), input: String, message: &Arc<Mutex<Option<String>>>) -> Outcome {
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| target(&input)));
+        let _ = tx.send(result.is_ok());
+    });
+
+    match rx.recv_timeout(TIMEOUT) {
+        Ok(true) => Outcome::Ok,
+        Ok(false) => Outcome::Panic(
+            message
+                .lock()
+                .ok()
+                .and_then(|mut m| m.take())
+                .unwrap_or_else(|| "unknown panic".into()),
+        ),
+        Err(_) => Outcome::Timeout,
+    }
+}
+
+/// Number of iterations if the solution was started with `--fuzz`, set via `--iterations <n>` (default: 10000).
+pub fn get_iterations() -> Option<usize> {
+    let args: Vec<String> = env::args().collect();
+
+    if !args.iter().any(|x| x == "--fuzz") {
+        return None;
+    }
+
+    let iterations = args
+        .iter()
+        .position(|x| x == "--iterations")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|x| x.parse().ok())
+        .unwrap_or(10_000);
+
+    Some(iterations)
+}
+
+/// Fuzz the parts of a solution with `iterations` mutated inputs.
+pub fn run_fuzz(day: Day, targets: &[FuzzTarget], iterations: usize) {
+    let fuzz_dir = get_fuzz_dir(day);
+    let current_path = fuzz_dir.join(CURRENT_INPUT);
+
+    if let Ok(input) = fs::read(&current_path) {
+        let path = save_input(day, "abort", &input);
+        println!(
+            "{ANSI_BOLD}The previous fuzzing run aborted{ANSI_RESET} (e.g. on a stack overflow), its input was saved to {}.\n",
+            path.display()
+        );
+        let _ = fs::remove_file(&current_path);
+    }
+
+    let corpus = read_corpus(day);
+    let max_len = corpus.iter().map(Vec::len).max().unwrap_or(0).max(64) * 2;
+
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64);
+    let mut rng = Rng::new(seed);
+
+    println!(
+        "Fuzzing day {day} with {iterations} inputs mutated from {} example file(s).",
+        corpus.len()
+    );
+
+    // record panic messages instead of printing them, the location identifies a crash.
+    let message: Arc<Mutex<Option<String>>> = Arc::new(Mutex::new(None));
+    let hook_message = Arc::clone(&message);
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if let Ok(mut m) = hook_message.lock() {
+            *m = Some(info.to_string());
+        }
+    }));
+
+    let _ = fs::create_dir_all(&fuzz_dir);
+
+    let mut seen: HashSet<(u8, String)> = HashSet::new();
+    let mut crashes = 0;
+    let timer = Instant::now();
+
+    'outer: for _ in 0..iterations {
+        let mut input = rng.choose(&corpus).clone();
+        for _ in 0..=rng.index(4) {
+            mutate(&mut rng, &mut input, max_len);
+        }
+
+        let _ = fs::write(&current_path, &input);
+
+        for (part, target) in targets {
+            let text = String::from_utf8_lossy(&input).to_string();
+
+            match run_case(*target, text, &message) {
+                Outcome::Ok => {}
+                Outcome::Panic(msg) => {
+                    crashes += 1;
+                    // the first line of a panic message is its location, report every location once.
+                    let location = msg.lines().next().unwrap_or_default().to_string();
+                    if seen.insert((*part, location)) {
+                        let path = save_input(day, &format!("crash-part{part}"), &input);
+                        println!("\n{ANSI_BOLD}Part {part} panicked:{ANSI_RESET} {msg}");
+                        println!("{ANSI_ITALIC}saved input to {}{ANSI_RESET}", path.display());
+                    }
+                }
+                Outcome::Timeout => {
+                    let path = save_input(day, &format!("timeout-part{part}"), &input);
+                    println!(
+                        "\n{ANSI_BOLD}Part {part} did not finish within {TIMEOUT:?}.{ANSI_RESET}"
+                    );
+                    println!("{ANSI_ITALIC}saved input to {}{ANSI_RESET}", path.display());
+                    println!("Stopping, the hanging thread cannot be cancelled.");
+                    crashes += 1;
+                    break 'outer;
+                }
+            }
+        }
+    }
+
+    let _ = fs::remove_file(&current_path);
+    panic::set_hook(default_hook);
+
+    println!(
+        "\n{crashes} crash(es), {} distinct, in {:.1?}.",
+        seen.len(),
+        timer.elapsed()
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{hash, mutate};
+    use crate::utils::testing::Rng;
+
+    #[test]
+    fn mutates_within_bounds() {
+        let mut rng = Rng::new(7);
+        let mut input = b"seeds: 79 14 55 13\n\nseed-to-soil map:\n50 98 2\n".to_vec();
+        for _ in 0..1000 {
+            mutate(&mut rng, &mut input, 128);
+            assert!(input.len() <= 128);
+        }
+    }
+
+    #[test]
+    fn hashes_inputs() {
+        assert_eq!(hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_ne!(hash(b"a"), hash(b"b"));
+    }
+}
@@ -0,0 +1,157 @@
+/// Module that renders the progression of stored benchmarks over time.
+use crate::template::machine::Machine;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+static SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Renders values as a sparkline. Uses a log scale, as optimizations tend to improve runtime by orders of magnitude.
+/// The scale spans at least one order of magnitude, so that measurement noise does not show up as a full-height spike.
+pub fn sparkline(values: &[f64]) -> String {
+    let logs: Vec<f64> = values.iter().map(|v| v.max(1.0).log10()).collect();
+
+    let min = logs.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = logs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let range = (max - min).max(1.0);
+
+    logs.iter()
+        .map(|v| {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let index = (((v - min) / range) * (SPARKS.len() - 1) as f64).round() as usize;
+            SPARKS[index]
+        })
+        .collect()
+}
+
+/// Sparkline of the total runtime of every recorded timing of a day.
+pub fn day_sparkline(timings: &Timings, day: Day) -> Option<String> {
+    let history = timings.day_history(day);
+    if history.len() < 2 {
+        return None;
+    }
+    let values: Vec<f64> = history.iter().map(|t| t.total_nanos).collect();
+    Some(sparkline(&values))
+}
+
+/// Formats a unix timestamp as an UTC date, e.g. `2023-12-03 14:05`.
+pub fn format_timestamp(timestamp: u64) -> String {
+    // see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    #[allow(clippy::cast_possible_wrap)]
+    let days = (timestamp / 86400) as i64 + 719_468;
+    let seconds = timestamp % 86400;
+
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3600,
+        (seconds % 3600) / 60
+    )
+}
+
+fn format_change(previous: &Timing, current: &Timing) -> String {
+    if previous.total_nanos <= 0.0 {
+        return String::new();
+    }
+    let change = (current.total_nanos / previous.total_nanos - 1.0) * 100.0;
+    format!(" ({change:+.1}%)")
+}
+
+fn format_entry(timing: &Timing, previous: Option<&Timing>) -> String {
+    let date = timing
+        .timestamp
+        .map_or_else(|| "unknown date".into(), format_timestamp);
+
+    let commit = timing
+        .machine
+        .as_ref()
+        .and_then(|m| m.commit.clone())
+        .unwrap_or_else(|| "-".into());
+
+    format!(
+        "{date:<16}  {commit:<13}  Part 1: {:<10}  Part 2: {:<10}  Total: {:.2}ms{}",
+        timing.part_1.as_deref().unwrap_or("-"),
+        timing.part_2.as_deref().unwrap_or("-"),
+        timing.total_nanos / 1_000_000_f64,
+        previous.map_or_else(String::new, |p| format_change(p, timing)),
+    )
+}
+
+/// Renders the progression of a day's timings, grouped by machine.
+pub fn report(day: Day, timings: &Timings) -> Vec<String> {
+    let history = timings.day_history(day);
+
+    if history.is_empty() {
+        return vec![format!("No timings stored for day {day}.")];
+    }
+
+    let mut machines: Vec<Option<String>> = vec![];
+    for timing in &history {
+        let machine_id = timing.machine_id();
+        if !machines.contains(&machine_id) {
+            machines.push(machine_id);
+        }
+    }
+
+    let mut lines = vec![format!("{ANSI_BOLD}Day {day}{ANSI_RESET}"), "------".into()];
+
+    for machine_id in machines {
+        let entries: Vec<&Timing> = history
+            .iter()
+            .filter(|t| t.machine_id() == machine_id)
+            .copied()
+            .collect();
+
+        lines.push(String::new());
+        lines.push(format!(
+            "{ANSI_ITALIC}{}{ANSI_RESET}",
+            entries
+                .last()
+                .and_then(|t| t.machine.as_ref())
+                .map_or_else(|| "Unknown machine".into(), Machine::describe)
+        ));
+
+        let mut previous: Option<&Timing> = None;
+        for timing in &entries {
+            lines.push(format_entry(timing, previous));
+            previous = Some(timing);
+        }
+
+        if entries.len() > 1 {
+            let values: Vec<f64> = entries.iter().map(|t| t.total_nanos).collect();
+            lines.push(format!("Trend: {}", sparkline(&values)));
+        }
+    }
+
+    lines
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_timestamp, sparkline};
+
+    #[test]
+    fn renders_sparklines() {
+        assert_eq!(sparkline(&[3.4e9, 28.2e6]), "█▁");
+        assert_eq!(sparkline(&[1e3, 1e6, 1e9]), "▁▅█");
+        assert_eq!(sparkline(&[5.0, 5.0]), "▁▁");
+        assert_eq!(sparkline(&[816.0, 842.0, 790.0]), "▁▁▁");
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_701_612_300), "2023-12-03 14:05");
+        assert_eq!(format_timestamp(1_709_208_000), "2024-02-29 12:00");
+    }
+}
@@ -0,0 +1,390 @@
+/// Module that fetches, caches and renders private leaderboards.
+/// Responses are cached in `data/leaderboard` for 15 minutes, the interval adventofcode.com asks API users to respect.
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use tinyjson::JsonValue;
+
+use crate::template::aoc_cli;
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+const CACHE_DURATION: Duration = Duration::from_secs(15 * 60);
+
+/// Unix timestamps of the stars of a day, part 1 and part 2.
+pub type DayStars = [Option<u64>; 2];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Member {
+    pub id: u64,
+    pub name: String,
+    pub local_score: u64,
+    pub stars: u64,
+    pub days: BTreeMap<u8, DayStars>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub year: u16,
+    /// Members, ranked by local score.
+    pub members: Vec<Member>,
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn get_number(json: &HashMap<String, JsonValue>, key: &str) -> Option<u64> {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    json.get(key)
+        .and_then(|v| v.get::<f64>())
+        .map(|n| *n as u64)
+}
+
+fn parse_days(json: Option<&JsonValue>) -> BTreeMap<u8, DayStars> {
+    let Some(days) = json.and_then(|v| v.get::<HashMap<String, JsonValue>>()) else {
+        return BTreeMap::new();
+    };
+
+    days.iter()
+        .filter_map(|(day, parts)| {
+            let parts = parts.get::<HashMap<String, JsonValue>>()?;
+            let star = |part: &str| {
+                parts
+                    .get(part)
+                    .and_then(|p| p.get::<HashMap<String, JsonValue>>())
+                    .and_then(|p| get_number(p, "get_star_ts"))
+            };
+            Some((day.parse().ok()?, [star("1"), star("2")]))
+        })
+        .collect()
+}
+
+impl TryFrom<&str> for Leaderboard {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json: JsonValue = value
+            .parse()
+            .map_err(|_| "response is not valid JSON, is the session cookie valid?")?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected leaderboard to be an object.")?;
+
+        let year = json
+            .get("event")
+            .and_then(|e| e.get::<String>())
+            .and_then(|e| e.parse().ok())
+            .ok_or("expected leaderboard to have an event.")?;
+
+        let members = json
+            .get("members")
+            .and_then(|m| m.get::<HashMap<String, JsonValue>>())
+            .ok_or("expected leaderboard to have members.")?;
+
+        let mut members: Vec<Member> = members
+            .values()
+            .filter_map(|member| {
+                let member = member.get::<HashMap<String, JsonValue>>()?;
+                let id = get_number(member, "id")?;
+                let name = member
+                    .get("name")
+                    .and_then(|n| n.get::<String>())
+                    .cloned()
+                    .unwrap_or_else(|| format!("(anonymous user #{id})"));
+
+                Some(Member {
+                    id,
+                    name,
+                    local_score: get_number(member, "local_score").unwrap_or(0),
+                    stars: get_number(member, "stars").unwrap_or(0),
+                    days: parse_days(member.get("completion_day_level")),
+                })
+            })
+            .collect();
+
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.id.cmp(&b.id))
+        });
+
+        Ok(Leaderboard { year, members })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Unix timestamp at which a puzzle unlocks: midnight EST (UTC-5) on the day in december.
+pub fn unlock_timestamp(year: u16, day: u8) -> u64 {
+    // see: http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let year = u64::from(year);
+    let (month, day) = (12, u64::from(day));
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month - 3) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    days * 86400 + 5 * 3600
+}
+
+/// Formats a duration in seconds, e.g. `01:02:03` or `2d 01:02:03`.
+pub fn format_seconds(seconds: u64) -> String {
+    let days = seconds / 86400;
+    let time = format!(
+        "{:02}:{:02}:{:02}",
+        (seconds % 86400) / 3600,
+        (seconds % 3600) / 60,
+        seconds % 60
+    );
+    if days > 0 {
+        format!("{days}d {time}")
+    } else {
+        time
+    }
+}
+
+fn day_symbol(stars: Option<&DayStars>) -> char {
+    match stars {
+        Some([Some(_), Some(_)]) => '★',
+        Some([Some(_), None]) => '☆',
+        _ => '·',
+    }
+}
+
+/// Latest day anybody got a star on.
+pub fn latest_day(leaderboard: &Leaderboard) -> Option<u8> {
+    leaderboard
+        .members
+        .iter()
+        .flat_map(|m| m.days.keys())
+        .max()
+        .copied()
+}
+
+/// Ranked table of all members, with a symbol per day: `★` both stars, `☆` first star, `·` none.
+pub fn render_ranking(leaderboard: &Leaderboard) -> Vec<String> {
+    let width = leaderboard
+        .members
+        .iter()
+        .map(|m| m.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    let days = latest_day(leaderboard).unwrap_or(0);
+
+    let day_header: String = (1..=days).map(|d| char::from(b'0' + d % 10)).collect();
+    let mut lines = vec![format!(
+        "{ANSI_BOLD}{:>3}  {:<width$}  {:>5}  {:>5}  {day_header}{ANSI_RESET}",
+        "#", "Name", "Score", "Stars"
+    )];
+
+    for (rank, member) in leaderboard.members.iter().enumerate() {
+        let days: String = (1..=days)
+            .map(|d| day_symbol(member.days.get(&d)))
+            .collect();
+        lines.push(format!(
+            "{:>3}  {:<width$}  {:>5}  {:>5}  {days}",
+            rank + 1,
+            member.name,
+            member.local_score,
+            member.stars
+        ));
+    }
+
+    lines
+}
+
+/// Per-member star times of a day, measured from the unlock, and the delta between both parts.
+pub fn render_day(leaderboard: &Leaderboard, day: u8) -> Vec<String> {
+    let unlock = unlock_timestamp(leaderboard.year, day);
+
+    let mut members: Vec<(&Member, DayStars)> = leaderboard
+        .members
+        .iter()
+        .filter_map(|m| Some((m, *m.days.get(&day)?)))
+        .collect();
+    // fastest part 2 first, then fastest part 1.
+    members.sort_by_key(|(_, [one, two])| (two.unwrap_or(u64::MAX), one.unwrap_or(u64::MAX)));
+
+    let width = members
+        .iter()
+        .map(|(m, _)| m.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+
+    let time = |ts: Option<u64>| {
+        ts.map_or_else(
+            || "-".into(),
+            |ts| format_seconds(ts.saturating_sub(unlock)),
+        )
+    };
+
+    let mut lines = vec![format!(
+        "{ANSI_BOLD}{:<width$}  {:>12}  {:>12}  {:>12}{ANSI_RESET}",
+        "Name", "Part 1", "Part 2", "Delta"
+    )];
+
+    for (member, [one, two]) in &members {
+        let delta = match (one, two) {
+            (Some(one), Some(two)) => format!("+{}", format_seconds(two.saturating_sub(*one))),
+            _ => "-".into(),
+        };
+        lines.push(format!(
+            "{:<width$}  {:>12}  {:>12}  {:>12}",
+            member.name,
+            time(*one),
+            time(*two),
+            delta
+        ));
+    }
+
+    if members.is_empty() {
+        lines.push(format!("{ANSI_ITALIC}Nobody has a star yet.{ANSI_RESET}"));
+    }
+
+    lines
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Leaderboard ids are numbers, anything else must not end up in a path or a URL.
+pub fn is_valid_id(id: &str) -> bool {
+    !id.is_empty() && id.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn get_cache_path(year: u16, id: &str) -> PathBuf {
+    PathBuf::from("data")
+        .join("leaderboard")
+        .join(format!("{year}-{id}.json"))
+}
+
+fn read_cache(path: &PathBuf) -> Option<(String, Duration)> {
+    let age = fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok())?;
+    Some((fs::read_to_string(path).ok()?, age))
+}
+
+/// Reads a leaderboard from the cache, or fetches it if the cache is older than 15 minutes.
+/// Returns the leaderboard and the age of the cached response, if it was cached.
+pub fn load(id: &str) -> Result<(Leaderboard, Option<Duration>), String> {
+    if !is_valid_id(id) {
+        return Err(format!("Invalid leaderboard id {id:?}, expected a number."));
+    }
+    let year = aoc_cli::get_year().ok_or("AOC_YEAR is not set.")?;
+    let path = get_cache_path(year, id);
+    let cached = read_cache(&path);
+
+    if let Some((json, age)) = &cached {
+        if *age < CACHE_DURATION {
+            return Ok((Leaderboard::try_from(json.as_str())?, Some(*age)));
+        }
+    }
+
+    match aoc_cli::private_leaderboard(id) {
+        Ok(json) => {
+            let leaderboard = Leaderboard::try_from(json.as_str())?;
+            if let Err(e) = path
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|()| fs::write(&path, &json))
+            {
+                eprintln!("Failed to cache leaderboard: {e}");
+            }
+            Ok((leaderboard, None))
+        }
+        // an outdated leaderboard is better than none.
+        Err(e) => match cached {
+            Some((json, age)) => {
+                eprintln!("Failed to fetch leaderboard ({e}), showing the cached one.");
+                Ok((Leaderboard::try_from(json.as_str())?, Some(age)))
+            }
+            None => Err(e.to_string()),
+        },
+    }
+}
+
+/// Minutes until a cached response may be refreshed.
+pub fn minutes_until_refresh(age: Duration) -> u64 {
+    CACHE_DURATION.saturating_sub(age).as_secs().div_ceil(60)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_seconds, is_valid_id, render_day, unlock_timestamp, Leaderboard};
+
+    const LEADERBOARD: &str = r#"{
+        "owner_id": 1,
+        "event": "2023",
+        "members": {
+            "1": {
+                "id": 1, "name": "alice", "stars": 3, "local_score": 10, "global_score": 0,
+                "completion_day_level": {
+                    "1": { "1": { "get_star_ts": 1701407100, "star_index": 0 }, "2": { "get_star_ts": 1701408000, "star_index": 1 } },
+                    "2": { "1": { "get_star_ts": 1701493500, "star_index": 2 } }
+                }
+            },
+            "2": { "id": 2, "name": null, "stars": 0, "local_score": 0, "global_score": 0, "completion_day_level": {} },
+            "3": {
+                "id": 3, "name": "bob", "stars": 2, "local_score": 12, "global_score": 0,
+                "completion_day_level": {
+                    "1": { "1": { "get_star_ts": 1701406920, "star_index": 0 }, "2": { "get_star_ts": 1701407040, "star_index": 1 } }
+                }
+            }
+        }
+    }"#;
+
+    #[test]
+    fn parses_and_ranks_members() {
+        let leaderboard = Leaderboard::try_from(LEADERBOARD).unwrap();
+        assert_eq!(leaderboard.year, 2023);
+
+        let names: Vec<&str> = leaderboard
+            .members
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["bob", "alice", "(anonymous user #2)"]);
+
+        let alice = &leaderboard.members[1];
+        assert_eq!(alice.days[&1], [Some(1_701_407_100), Some(1_701_408_000)]);
+        assert_eq!(alice.days[&2], [Some(1_701_493_500), None]);
+    }
+
+    #[test]
+    fn computes_unlock_timestamps() {
+        // 2023-12-01 05:00:00 UTC
+        assert_eq!(unlock_timestamp(2023, 1), 1_701_406_800);
+        assert_eq!(unlock_timestamp(2023, 25), 1_701_406_800 + 24 * 86400);
+    }
+
+    #[test]
+    fn validates_ids() {
+        assert!(is_valid_id("123456"));
+        assert!(!is_valid_id(""));
+        assert!(!is_valid_id("../../x"));
+        assert!(!is_valid_id("12/34"));
+    }
+
+    #[test]
+    fn formats_seconds() {
+        assert_eq!(format_seconds(3723), "01:02:03");
+        assert_eq!(format_seconds(2 * 86400 + 59), "2d 00:00:59");
+    }
+
+    #[test]
+    fn renders_star_times_and_deltas() {
+        let leaderboard = Leaderboard::try_from(LEADERBOARD).unwrap();
+        let lines = render_day(&leaderboard, 1);
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].starts_with("bob"));
+        assert!(lines[1].ends_with("    00:02:00      00:04:00     +00:02:00"));
+        assert!(lines[2].ends_with("    00:05:00      00:20:00     +00:15:00"));
+    }
+}
@@ -0,0 +1,188 @@
+/// Module that fingerprints the machine and build that benchmarks are recorded on.
+use std::{collections::HashMap, env, fs, process::Command, thread};
+use tinyjson::JsonValue;
+
+/// Describes where a benchmark was recorded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Machine {
+    pub cpu: String,
+    pub cores: usize,
+    pub rustc: String,
+    pub profile: String,
+    pub commit: Option<String>,
+}
+
+impl Machine {
+    /// Fingerprint the current machine. `profile` is the cargo profile the solutions are built with.
+    pub fn detect(profile: &str) -> Self {
+        Machine {
+            cpu: get_cpu_model().unwrap_or_else(|| "unknown".into()),
+            cores: thread::available_parallelism().map_or(1, std::num::NonZero::get),
+            rustc: run_and_read("rustc", &["--version"])
+                .map(|x| x.trim_start_matches("rustc ").to_string())
+                .unwrap_or_else(|| "unknown".into()),
+            profile: profile.into(),
+            commit: get_commit(),
+        }
+    }
+
+    /// Identifies the hardware, independent of toolchain and commit.
+    /// Timings are kept apart per id, so results from different machines do not overwrite each other.
+    pub fn id(&self) -> String {
+        let unit = if self.cores == 1 { "core" } else { "cores" };
+        format!("{} ({} {unit})", self.cpu, self.cores)
+    }
+
+    /// Human-readable description of hardware and toolchain.
+    pub fn describe(&self) -> String {
+        format!(
+            "{}, rustc {}, {} profile",
+            self.id(),
+            self.rustc,
+            self.profile
+        )
+    }
+}
+
+fn run_and_read(cmd: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(cmd).args(args).output().ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let s = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if s.is_empty() {
+        None
+    } else {
+        Some(s)
+    }
+}
+
+fn get_cpu_model() -> Option<String> {
+    if cfg!(target_os = "macos") {
+        return run_and_read("sysctl", &["-n", "machdep.cpu.brand_string"]);
+    }
+
+    if cfg!(target_os = "windows") {
+        return env::var("PROCESSOR_IDENTIFIER").ok();
+    }
+
+    // NOTE: ARM-based linux does not always list `model name` in `/proc/cpuinfo`, `lscpu` does.
+    let from_field = |s: String, field: &str| {
+        s.lines()
+            .filter_map(|l| l.split_once(':'))
+            .find(|(key, _)| key.trim() == field)
+            .map(|(_, value)| value.trim().to_string())
+    };
+
+    fs::read_to_string("/proc/cpuinfo")
+        .ok()
+        .and_then(|s| from_field(s, "model name"))
+        .or_else(|| run_and_read("lscpu", &[]).and_then(|s| from_field(s, "Model name")))
+}
+
+fn get_commit() -> Option<String> {
+    let commit = run_and_read("git", &["rev-parse", "--short", "HEAD"])?;
+
+    let is_dirty = Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .is_ok_and(|o| !o.stdout.is_empty());
+
+    if is_dirty {
+        Some(format!("{commit}-dirty"))
+    } else {
+        Some(commit)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Machine> for JsonValue {
+    fn from(value: &Machine) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("cpu".into(), JsonValue::String(value.cpu.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("cores".into(), JsonValue::Number(value.cores as f64));
+        map.insert("rustc".into(), JsonValue::String(value.rustc.clone()));
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert(
+            "commit".into(),
+            match &value.commit {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Machine {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected machine to be a JSON object.")?;
+
+        let get_string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected machine.{key} to be a string."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let cores = json
+            .get("cores")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as usize)
+            .ok_or("Expected machine.cores to be a number.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected machine.commit to be null or string.")?;
+
+        Ok(Machine {
+            cpu: get_string("cpu")?,
+            cores,
+            rustc: get_string("rustc")?,
+            profile: get_string("profile")?,
+            commit: commit.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::Machine;
+
+    #[test]
+    fn roundtrips_json() {
+        let machine = Machine {
+            cpu: "Apple M4 Pro".into(),
+            cores: 12,
+            rustc: "1.83.0 (90b35a623 2024-11-26)".into(),
+            profile: "release".into(),
+            commit: Some("abc1234".into()),
+        };
+        let json = JsonValue::from(&machine);
+        assert_eq!(Machine::try_from(&json).unwrap(), machine);
+        assert_eq!(machine.id(), "Apple M4 Pro (12 cores)");
+    }
+
+    #[test]
+    fn detects_current_machine() {
+        let machine = Machine::detect("release");
+        assert_eq!(machine.profile, "release");
+        assert!(machine.cores >= 1);
+    }
+}
@@ -0,0 +1,310 @@
+/// Module that reads and writes per-day metadata, stored in `data/meta/NN.json`:
+///
+/// ```json
+/// {
+///   "title": "Trebuchet?!",
+///   "tags": ["parsing", "strings"],
+///   "example": { "part_1": "142", "part_2": "281" },
+///   "answers": { "part_1": null, "part_2": null },
+///   "notes": ["pt1: 4.8ms & pt2: 7.8ms, moved allocation out of the loop."]
+/// }
+/// ```
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+/// Answers of both parts, stored as strings so that any result type fits.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answers {
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            _ => self.part_2.as_deref(),
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: Option<String>) {
+        match part {
+            1 => self.part_1 = answer,
+            _ => self.part_2 = answer,
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        usize::from(self.part_1.is_some()) + usize::from(self.part_2.is_some())
+    }
+}
+
+/// Metadata of a single day.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Meta {
+    pub title: Option<String>,
+    /// Topics of the puzzle, e.g. `grid`, `dp` or `graph`.
+    pub tags: Vec<String>,
+    /// Expected answers of the examples in `data/examples`.
+    pub example: Answers,
+    /// Answers accepted for the real input.
+    pub answers: Answers,
+    /// Free-form notes, e.g. on benchmarks of previous approaches.
+    pub notes: Vec<String>,
+}
+
+#[must_use]
+pub fn get_path(day: Day) -> PathBuf {
+    PathBuf::from("data")
+        .join("meta")
+        .join(format!("{day}.json"))
+}
+
+impl Meta {
+    /// Read the metadata of a day. Days without a metadata file get empty metadata.
+    pub fn read(day: Day) -> Result<Self, String> {
+        match fs::read_to_string(get_path(day)) {
+            Ok(s) => {
+                Meta::try_from(s.as_str()).map_err(|e| format!("{}: {e}", get_path(day).display()))
+            }
+            Err(_) => Ok(Meta::default()),
+        }
+    }
+
+    /// Like [`Meta::read`], but reports invalid files and continues with empty metadata.
+    pub fn read_or_default(day: Day) -> Self {
+        Meta::read(day).unwrap_or_else(|e| {
+            eprintln!("Invalid metadata file {e}");
+            Meta::default()
+        })
+    }
+
+    pub fn store(&self, day: Day) -> Result<(), std::io::Error> {
+        let path = get_path(day);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_json())
+    }
+
+    /// Read, modify and store the metadata of a day. Does not touch the file if nothing changed.
+    pub fn update(day: Day, f: impl FnOnce(&mut Meta)) -> Result<(), String> {
+        let meta = Meta::read(day)?;
+        let mut updated = meta.clone();
+        f(&mut updated);
+        if updated != meta {
+            updated.store(day).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    /// Serializes to pretty-printed JSON with a stable key order, so that the files diff well.
+    pub fn to_json(&self) -> String {
+        let string = |s: &str| JsonValue::String(s.into()).stringify().unwrap_or_default();
+        let optional = |s: &Option<String>| s.as_deref().map_or("null".into(), string);
+        let list = |items: &[String]| {
+            if items.is_empty() {
+                "[]".to_string()
+            } else {
+                let items: Vec<String> =
+                    items.iter().map(|x| format!("    {}", string(x))).collect();
+                format!("[\n{}\n  ]", items.join(",\n"))
+            }
+        };
+        let answers = |a: &Answers| {
+            format!(
+                "{{ \"part_1\": {}, \"part_2\": {} }}",
+                optional(&a.part_1),
+                optional(&a.part_2)
+            )
+        };
+
+        [
+            "{".to_string(),
+            format!("  \"title\": {},", optional(&self.title)),
+            format!("  \"tags\": {},", list(&self.tags)),
+            format!("  \"example\": {},", answers(&self.example)),
+            format!("  \"answers\": {},", answers(&self.answers)),
+            format!("  \"notes\": {}", list(&self.notes)),
+            "}".to_string(),
+            String::new(),
+        ]
+        .join("\n")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Reads the puzzle title from a puzzle description downloaded by aoc-cli, e.g. `## --- Day 1: Trebuchet?! ---`.
+pub fn title_from_puzzle(puzzle: &str) -> Option<String> {
+    let line = puzzle.lines().find(|l| l.contains("--- Day "))?;
+    let title = line.split_once(": ")?.1.trim_end_matches('-').trim();
+    Some(title.to_string())
+}
+
+/// Reads the accepted answers from a puzzle description, e.g. ``Your puzzle answer was `142`.``
+pub fn answers_from_puzzle(puzzle: &str) -> Answers {
+    let mut answers = puzzle
+        .split("Your puzzle answer was")
+        .skip(1)
+        .filter_map(|s| s.split('`').nth(1))
+        .map(ToString::to_string);
+
+    Answers {
+        part_1: answers.next(),
+        part_2: answers.next(),
+    }
+}
+
+/// Title of a day from its metadata, falling back to the downloaded puzzle description.
+pub fn get_title(day: Day) -> Option<String> {
+    Meta::read_or_default(day).title.or_else(|| {
+        let puzzle = fs::read_to_string(format!("data/puzzles/{day}.md")).ok()?;
+        title_from_puzzle(&puzzle)
+    })
+}
+
+/// Copies title and accepted answers from `data/puzzles/NN.md` into the metadata of a day.
+pub fn sync_from_puzzle(day: Day) -> Result<(), String> {
+    let Ok(puzzle) = fs::read_to_string(format!("data/puzzles/{day}.md")) else {
+        return Ok(());
+    };
+
+    Meta::update(day, |meta| {
+        if let Some(title) = title_from_puzzle(&puzzle) {
+            meta.title = Some(title);
+        }
+        let answers = answers_from_puzzle(&puzzle);
+        for part in [1, 2] {
+            if let Some(answer) = answers.get(part) {
+                meta.answers.set(part, Some(answer.into()));
+            }
+        }
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn get_string(json: &HashMap<String, JsonValue>, key: &str) -> Result<Option<String>, String> {
+    match json.get(key) {
+        None | Some(JsonValue::Null) => Ok(None),
+        Some(JsonValue::String(s)) => Ok(Some(s.clone())),
+        // answers are often numbers, accept them as such.
+        Some(JsonValue::Number(n)) => Ok(Some(n.to_string())),
+        Some(_) => Err(format!("Expected {key} to be null, a string or a number.")),
+    }
+}
+
+fn get_list(json: &HashMap<String, JsonValue>, key: &str) -> Result<Vec<String>, String> {
+    match json.get(key) {
+        None | Some(JsonValue::Null) => Ok(vec![]),
+        Some(JsonValue::Array(items)) => items
+            .iter()
+            .map(|x| {
+                x.get::<String>()
+                    .cloned()
+                    .ok_or(format!("Expected {key} to be a list of strings."))
+            })
+            .collect(),
+        Some(_) => Err(format!("Expected {key} to be a list of strings.")),
+    }
+}
+
+fn get_answers(json: &HashMap<String, JsonValue>, key: &str) -> Result<Answers, String> {
+    match json.get(key) {
+        None | Some(JsonValue::Null) => Ok(Answers::default()),
+        Some(JsonValue::Object(answers)) => Ok(Answers {
+            part_1: get_string(answers, "part_1")?,
+            part_2: get_string(answers, "part_2")?,
+        }),
+        Some(_) => Err(format!("Expected {key} to be an object.")),
+    }
+}
+
+impl TryFrom<&str> for Meta {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json: JsonValue = value.parse().map_err(|_| "not valid JSON.")?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        Ok(Meta {
+            title: get_string(json, "title")?,
+            tags: get_list(json, "tags")?,
+            example: get_answers(json, "example")?,
+            answers: get_answers(json, "answers")?,
+            notes: get_list(json, "notes")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{answers_from_puzzle, title_from_puzzle, Answers, Meta};
+
+    fn get_mock_meta() -> Meta {
+        Meta {
+            title: Some("Trebuchet?!".into()),
+            tags: vec!["parsing".into(), "strings".into()],
+            example: Answers {
+                part_1: Some("142".into()),
+                part_2: Some("281".into()),
+            },
+            answers: Answers::default(),
+            notes: vec!["pt1: 4.8ms & pt2: \"7.8ms\"".into()],
+        }
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let meta = get_mock_meta();
+        assert_eq!(Meta::try_from(meta.to_json().as_str()).unwrap(), meta);
+    }
+
+    #[test]
+    fn serializes_with_stable_order() {
+        let expected = [
+            "{",
+            "  \"title\": \"Trebuchet?!\",",
+            "  \"tags\": [",
+            "    \"parsing\",",
+            "    \"strings\"",
+            "  ],",
+            "  \"example\": { \"part_1\": \"142\", \"part_2\": \"281\" },",
+            "  \"answers\": { \"part_1\": null, \"part_2\": null },",
+            "  \"notes\": [",
+            "    \"pt1: 4.8ms & pt2: \\\"7.8ms\\\"\"",
+            "  ]",
+            "}",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(get_mock_meta().to_json(), expected);
+    }
+
+    #[test]
+    fn accepts_partial_documents() {
+        let meta = Meta::try_from(r#"{ "example": { "part_1": 142 } }"#).unwrap();
+        assert_eq!(meta.example.part_1, Some("142".into()));
+        assert_eq!(meta.title, None);
+        assert_eq!(meta.tags.len(), 0);
+    }
+
+    #[test]
+    fn reads_puzzle_description() {
+        let puzzle = "## \\--- Day 1: Trebuchet?! ---\n\nYour puzzle answer was `54990`.\n\n## \\--- Part Two ---\n\nYour puzzle answer was `54473`.";
+        assert_eq!(title_from_puzzle(puzzle), Some("Trebuchet?!".into()));
+        let answers = answers_from_puzzle(puzzle);
+        assert_eq!(answers.part_1, Some("54990".into()));
+        assert_eq!(answers.part_2, Some("54473".into()));
+    }
+}
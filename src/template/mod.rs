@@ -1,15 +1,27 @@
+use std::path::{Path, PathBuf};
 use std::{env, fs};
 
 pub mod aoc_cli;
 pub mod commands;
+pub mod fuzz;
 pub mod runner;
 
 pub use day::*;
+pub use solution::Solution;
 
 mod day;
+mod examples;
+mod history;
+mod leaderboard;
+mod machine;
+mod meta;
+mod progress;
 mod readme_benchmarks;
 mod run_multi;
+mod search;
+mod solution;
 mod timings;
+mod watch;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -36,9 +48,33 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     f.expect("could not open input file")
 }
 
+/// Path of the example a part is checked against: `NN-P.txt` if it exists, `NN.txt` otherwise.
+#[must_use]
+pub fn get_example_path(day: Day, part: u8) -> PathBuf {
+    let folder = Path::new("data").join("examples");
+    let part_path = folder.join(format!("{day}-{part}.txt"));
+    if part_path.exists() {
+        part_path
+    } else {
+        folder.join(format!("{day}.txt"))
+    }
+}
+
+/// Reads the puzzle input of a part. If the solution was started with `--example`, reads its example instead.
+#[must_use]
+pub fn read_input(day: Day, part: u8) -> String {
+    if env::args().any(|x| x == "--example") {
+        fs::read_to_string(get_example_path(day, part)).expect("could not open example file")
+    } else {
+        read_file("inputs", day)
+    }
+}
+
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Alternatively, the second parameter can be a type implementing [`template::Solution`](crate::template::Solution).
+/// Its input is then parsed once and shared by both parts.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,6 +86,41 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, $solution:ty) => {
+        /// The current day.
+        const DAY: $crate::template::Day = $crate::day!($day);
+
+        #[cfg(feature = "dhat-heap")]
+        #[global_allocator]
+        static ALLOC: dhat::Alloc = dhat::Alloc;
+
+        fn main() {
+            use $crate::template::runner::*;
+            use $crate::template::Solution;
+
+            if let Some(iterations) = $crate::template::fuzz::get_iterations() {
+                let targets: [$crate::template::fuzz::FuzzTarget; 2] = [
+                    (1, |input| { let _ = <$solution>::part_one(&<$solution>::parse(input)); }),
+                    (2, |input| { let _ = <$solution>::part_two(&<$solution>::parse(input)); }),
+                ];
+                $crate::template::fuzz::run_fuzz(DAY, &targets, iterations);
+                return;
+            }
+
+            let input = $crate::template::read_input(DAY, 1);
+            let (parsed, parse) = run_parse(<$solution>::parse, &input);
+            run_part_parsed(<$solution>::part_one, &parsed, &parse, DAY, 1);
+
+            // examples may differ between parts, the real input never does.
+            let input_2 = $crate::template::read_input(DAY, 2);
+            if input_2 == input {
+                run_part_parsed(<$solution>::part_two, &parsed, &parse, DAY, 2);
+            } else {
+                let (parsed, parse) = run_parse(<$solution>::parse, &input_2);
+                run_part_parsed(<$solution>::part_two, &parsed, &parse, DAY, 2);
+            }
+        }
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
         /// The current day.
@@ -61,8 +132,16 @@ macro_rules! solution {
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+
+            if let Some(iterations) = $crate::template::fuzz::get_iterations() {
+                let targets: &[$crate::template::fuzz::FuzzTarget] = &[
+                    $( ($part, |input| { let _ = $func(input); }), )*
+                ];
+                $crate::template::fuzz::run_fuzz(DAY, targets, iterations);
+                return;
+            }
+
+            $( run_part($func, &$crate::template::read_input(DAY, $part), DAY, $part); )*
         }
     };
 }
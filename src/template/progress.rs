@@ -0,0 +1,231 @@
+/// Module that works out which days are solved from local state and renders them as a calendar.
+use std::{
+    fs,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use crate::template::meta::Meta;
+use crate::template::readme_benchmarks::{get_path_for_bin, locate_table, Error};
+use crate::template::{all_days, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+static MARKER: &str = "<!--- progress calendar --->";
+
+/// State of a single day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DayProgress {
+    pub day: Day,
+    /// A solution file exists in `src/bin`.
+    pub has_solution: bool,
+    /// Number of answers accepted by adventofcode.com, read from the puzzle description in `data/puzzles`.
+    pub stars: u8,
+    /// Whether the tests of the solution pass. `None` if there is no solution or tests were not run.
+    pub tests_pass: Option<bool>,
+}
+
+impl DayProgress {
+    fn symbol(&self) -> &'static str {
+        match (self.has_solution, self.tests_pass) {
+            (false, _) => "·",
+            (true, Some(false)) => "✖",
+            (true, _) if self.stars == 0 => "…",
+            _ => "",
+        }
+    }
+}
+
+/// Counts the answers accepted for a puzzle. aoc-cli includes them in the puzzle description once a part is solved.
+pub fn count_stars(puzzle: &str) -> u8 {
+    let answers = puzzle.matches("Your puzzle answer was").count();
+    // day 25 only has one answer, its second star is awarded for completing all other days.
+    if puzzle.contains("Both parts of this puzzle are complete!") {
+        2
+    } else {
+        u8::try_from(answers.min(2)).unwrap_or(2)
+    }
+}
+
+fn run_tests(day: Day) -> bool {
+    Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &day.to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+/// Collects the progress of all days. Tests are run for every day that has a solution if `with_tests` is set.
+pub fn collect(with_tests: bool) -> Vec<DayProgress> {
+    all_days()
+        .map(|day| {
+            let has_solution = Path::new(&format!("src/bin/{day}.rs")).exists();
+
+            // answers recorded in the metadata count as well, puzzle descriptions are not always downloaded.
+            let recorded = u8::try_from(Meta::read_or_default(day).answers.count()).unwrap_or(2);
+            let stars = fs::read_to_string(format!("data/puzzles/{day}.md"))
+                .map_or(0, |puzzle| count_stars(&puzzle))
+                .max(recorded);
+
+            let tests_pass = if has_solution && with_tests {
+                Some(run_tests(day))
+            } else {
+                None
+            };
+
+            DayProgress {
+                day,
+                has_solution,
+                stars,
+                tests_pass,
+            }
+        })
+        .collect()
+}
+
+fn total_stars(progress: &[DayProgress]) -> u32 {
+    progress.iter().map(|p| u32::from(p.stars)).sum()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Renders the calendar for the terminal, five days per row.
+pub fn render_calendar(progress: &[DayProgress]) -> Vec<String> {
+    let mut lines = vec![];
+
+    for week in progress.chunks(5) {
+        let row: Vec<String> = week
+            .iter()
+            .map(|p| {
+                let status = format!("{}{}", "⭐".repeat(p.stars.into()), p.symbol());
+                let day = if p.stars == 2 {
+                    format!("{ANSI_BOLD}{:>2}{ANSI_RESET}", p.day.into_inner())
+                } else {
+                    format!("{:>2}", p.day.into_inner())
+                };
+                // pad manually, `format!` width counts chars and stars take up two columns in a terminal.
+                let columns = usize::from(p.stars) * 2 + p.symbol().chars().count();
+                let padding = " ".repeat(4 - columns.min(4));
+                format!("{day} {status}{padding}")
+            })
+            .collect();
+        lines.push(row.join("  "));
+    }
+
+    lines.push(String::new());
+    lines.push(format!(
+        "{ANSI_BOLD}{}{ANSI_RESET}/50 ⭐",
+        total_stars(progress)
+    ));
+    lines.push(format!(
+        "{ANSI_ITALIC}⭐ accepted answer  … solved, not submitted  ✖ tests failing  · not started{ANSI_RESET}"
+    ));
+
+    lines
+}
+
+fn construct_calendar(progress: &[DayProgress]) -> String {
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        "## Progress".into(),
+        String::new(),
+        "| | | | | |".into(),
+        "| :---: | :---: | :---: | :---: | :---: |".into(),
+    ];
+
+    for week in progress.chunks(5) {
+        let cells: Vec<String> = week
+            .iter()
+            .map(|p| {
+                let day = p.day.into_inner();
+                let label = if p.has_solution {
+                    format!("[{day}]({})", get_path_for_bin(p.day))
+                } else {
+                    day.to_string()
+                };
+                let status = format!("{}{}", "⭐".repeat(p.stars.into()), p.symbol());
+                format!("{label} {status}")
+            })
+            .collect();
+        lines.push(format!("| {} |", cells.join(" | ")));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("**{}/50 ⭐**", total_stars(progress)));
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, progress: &[DayProgress]) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_calendar(progress);
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(())
+}
+
+/// Replace the calendar between the progress markers in the readme.
+pub fn update(progress: &[DayProgress]) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, progress)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{count_stars, update_content, DayProgress, MARKER};
+    use crate::template::all_days;
+
+    fn get_mock_progress() -> Vec<DayProgress> {
+        all_days()
+            .map(|day| DayProgress {
+                day,
+                has_solution: day.into_inner() <= 3,
+                stars: match day.into_inner() {
+                    1 => 2,
+                    2 => 1,
+                    _ => 0,
+                },
+                tests_pass: match day.into_inner() {
+                    1 | 2 => Some(true),
+                    3 => Some(false),
+                    _ => None,
+                },
+            })
+            .collect()
+    }
+
+    #[test]
+    fn counts_stars() {
+        assert_eq!(count_stars("## --- Day 1: Trebuchet?! ---"), 0);
+        assert_eq!(count_stars("Your puzzle answer was `142`."), 1);
+        assert_eq!(
+            count_stars("Your puzzle answer was `142`.\nYour puzzle answer was `281`."),
+            2
+        );
+        assert_eq!(
+            count_stars("Your puzzle answer was `1`.\nBoth parts of this puzzle are complete! They provide two gold stars: **"),
+            2
+        );
+    }
+
+    #[test]
+    fn renders_calendar() {
+        let mut s = format!("foo\n{}\n{}\nbar", MARKER, MARKER);
+        update_content(&mut s, &get_mock_progress()).unwrap();
+
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(lines[2], "## Progress");
+        assert_eq!(
+            lines[6],
+            "| [1](./src/bin/01.rs) ⭐⭐ | [2](./src/bin/02.rs) ⭐ | [3](./src/bin/03.rs) ✖ | 4 · | 5 · |"
+        );
+        assert_eq!(lines[7], "| 6 · | 7 · | 8 · | 9 · | 10 · |");
+        assert_eq!(lines[12], "**3/50 ⭐**");
+        assert_eq!(lines[13], MARKER);
+        assert_eq!(lines[14], "bar");
+    }
+}
@@ -1,8 +1,13 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{env, fmt::Display, fs, io, path::Path, str::FromStr, time::Duration};
 
-use crate::template::timings::Timings;
+use strum_macros::EnumString;
+
+use crate::template::history;
+use crate::template::machine::Machine;
+use crate::template::meta;
+use crate::template::timings::{MemoryUsage, SampleStats, Timing, Timings};
 use crate::template::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
@@ -19,9 +24,18 @@ impl From<std::io::Error> for Error {
     }
 }
 
-pub struct TablePosition {
-    pos_start: usize,
-    pos_end: usize,
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+pub(crate) struct TablePosition {
+    pub pos_start: usize,
+    pub pos_end: usize,
 }
 
 #[must_use]
@@ -29,8 +43,10 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+/// Locates the section enclosed by two occurences of `marker`, including the markers.
+/// Shared with other generators that keep a section of the readme in sync.
+pub(crate) fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -51,55 +67,548 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
+/* -------------------------------------------------------------------------- */
+
+/// A column of the benchmark table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum Column {
+    Day,
+    /// Puzzle title, read from `data/meta` or `data/puzzles`.
+    Title,
+    Stars,
+    #[strum(serialize = "part_1")]
+    Part1,
+    #[strum(serialize = "part_2")]
+    Part2,
+    /// Parse time of solutions sharing their input between parts, only shown if recorded.
+    Parse,
+    Samples,
+    Median,
+    /// Standard deviation of the samples.
+    Spread,
+    /// Heap usage, only shown if recorded via `cargo time --memory`.
+    Memory,
+    /// Share of the total runtime.
+    Share,
+    /// Sparkline of the history, only shown if a day has more than one measurement.
+    Trend,
+}
+
+/// Order of the rows of the benchmark table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum Sort {
+    Day,
+    /// Slowest day first.
+    Time,
+}
+
+/// Where the benchmark table is written to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum Target {
+    /// Between the markers in `README.md`.
+    Readme,
+    /// Between the markers in `BENCHMARKS.md`, the file is created if missing.
+    Benchmarks,
+    /// A standalone `BENCHMARKS.html` page.
+    Html,
+}
+
+impl Target {
+    fn path(self) -> &'static str {
+        match self {
+            Target::Readme => "README.md",
+            Target::Benchmarks => "BENCHMARKS.md",
+            Target::Html => "BENCHMARKS.html",
+        }
+    }
+}
+
+/// Layout of the benchmark table.
+/// Configured via the `AOC_BENCHMARK_*` environment variables, see `.cargo/config.toml`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TableConfig {
+    pub columns: Vec<Column>,
+    pub sort: Sort,
+    /// Mark slow days with an emoji.
+    pub badges: bool,
+    pub targets: Vec<Target>,
+}
+
+impl Default for TableConfig {
+    fn default() -> Self {
+        TableConfig {
+            columns: vec![
+                Column::Day,
+                Column::Part1,
+                Column::Part2,
+                Column::Parse,
+                Column::Memory,
+                Column::Trend,
+            ],
+            sort: Sort::Day,
+            badges: false,
+            targets: vec![Target::Readme],
+        }
+    }
+}
+
+fn parse_list<T: FromStr>(key: &str, value: &str) -> Result<Vec<T>, Error> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .map(|x| {
+            x.parse()
+                .map_err(|_| Error::Parser(format!("{key}: unknown value `{x}`.")))
+        })
+        .collect()
+}
+
+impl TableConfig {
+    /// Read the table layout from the environment, falling back to the default layout for unset variables.
+    pub fn from_env() -> Result<Self, Error> {
+        let mut config = TableConfig::default();
+
+        if let Ok(value) = env::var("AOC_BENCHMARK_COLUMNS") {
+            config.columns = parse_list("AOC_BENCHMARK_COLUMNS", &value)?;
+        }
+
+        if let Ok(value) = env::var("AOC_BENCHMARK_SORT") {
+            config.sort = value.trim().parse().map_err(|_| {
+                Error::Parser(format!("AOC_BENCHMARK_SORT: unknown value `{value}`."))
+            })?;
+        }
+
+        if let Ok(value) = env::var("AOC_BENCHMARK_BADGES") {
+            config.badges = matches!(value.trim(), "true" | "1");
+        }
+
+        if let Ok(value) = env::var("AOC_BENCHMARK_TARGETS") {
+            config.targets = parse_list("AOC_BENCHMARK_TARGETS", &value)?;
+        }
+
+        Ok(config)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Part of the content of a table cell. Allows rendering the same table as markdown and html.
+#[derive(Clone, Debug)]
+enum Span {
+    Text(String),
+    Code(String),
+    Link(String, String),
+}
+
+type Cell = Vec<Span>;
+
+fn text(s: impl Into<String>) -> Cell {
+    vec![Span::Text(s.into())]
+}
+
+fn code(s: impl Into<String>) -> Cell {
+    vec![Span::Code(s.into())]
+}
+
+/// A table of timings recorded on a single machine.
+struct Section {
+    machine: Option<Machine>,
+    headers: Vec<(&'static str, &'static str)>,
+    rows: Vec<Vec<Cell>>,
+    total_millis: f64,
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+fn format_nanos(nanos: u64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos))
+}
+
+fn format_memory(memory: Option<MemoryUsage>) -> Cell {
+    memory.map_or_else(
+        || text("-"),
+        |m| {
+            vec![
+                Span::Code(format_bytes(m.max_bytes)),
+                Span::Text(" peak, ".into()),
+                Span::Code(format_bytes(m.total_bytes)),
+                Span::Text(format!(" total, {} allocs", m.total_blocks)),
+            ]
+        },
+    )
+}
+
+/// Formats a statistic of both parts, e.g. `100 / 5000`.
+fn format_samples(timing: &Timing, f: impl Fn(&SampleStats) -> Cell) -> Cell {
+    let format_part = |samples: Option<SampleStats>| samples.as_ref().map_or_else(|| text("-"), &f);
+
+    let mut cell = format_part(timing.part_1_samples);
+    cell.push(Span::Text(" / ".into()));
+    cell.extend(format_part(timing.part_2_samples));
+    cell
+}
+
+fn get_badge(timing: &Timing) -> Option<&'static str> {
+    match timing.total_nanos {
+        x if x >= 1e9 => Some("🐌"),
+        x if x >= 1e8 => Some("🐢"),
+        _ => None,
+    }
+}
+
+fn is_column_visible(column: Column, timings: &Timings) -> bool {
+    match column {
+        Column::Parse => timings.data.iter().any(|t| t.parse.is_some()),
+        Column::Memory => timings.has_memory(),
+        Column::Trend => timings
+            .data
+            .iter()
+            .any(|t| history::day_sparkline(timings, t.day).is_some()),
+        _ => true,
+    }
+}
+
+fn column_headers(column: Column) -> Vec<(&'static str, &'static str)> {
+    match column {
+        Column::Day => vec![("Day", ":---:")],
+        Column::Title => vec![("Title", ":---")],
+        Column::Stars => vec![("Stars", ":---:")],
+        Column::Part1 => vec![("Part 1", ":---:")],
+        // NOTE: the trailing space matches tables written by previous versions of the template.
+        Column::Part2 => vec![("Part 2", ":---: ")],
+        Column::Parse => vec![("Parse", ":---:")],
+        Column::Samples => vec![("Samples", ":---:")],
+        Column::Median => vec![("Median", ":---:")],
+        Column::Spread => vec![("Spread (σ)", ":---:")],
+        Column::Memory => vec![("Part 1 Heap", ":---:"), ("Part 2 Heap", ":---:")],
+        Column::Share => vec![("Share", "---:")],
+        Column::Trend => vec![("Trend", ":---:")],
+    }
+}
+
+fn column_cells(
+    column: Column,
+    timing: &Timing,
+    timings: &Timings,
+    total_nanos: f64,
+    config: &TableConfig,
+) -> Vec<Cell> {
+    match column {
+        Column::Day => {
+            let mut cell = vec![Span::Link(
+                format!("Day {}", timing.day.into_inner()),
+                get_path_for_bin(timing.day),
+            )];
+            if let Some(badge) = get_badge(timing).filter(|_| config.badges) {
+                cell.push(Span::Text(format!(" {badge}")));
+            }
+            vec![cell]
+        }
+        Column::Title => vec![meta::get_title(timing.day).map_or_else(|| text("-"), text)],
+        Column::Stars => {
+            let stars = usize::from(timing.part_1.is_some()) + usize::from(timing.part_2.is_some());
+            vec![text("⭐".repeat(stars))]
+        }
+        Column::Part1 => vec![code(timing.part_1.as_deref().unwrap_or("-"))],
+        Column::Part2 => vec![code(timing.part_2.as_deref().unwrap_or("-"))],
+        Column::Parse => vec![code(timing.parse.as_deref().unwrap_or("-"))],
+        Column::Samples => vec![format_samples(timing, |s| text(s.count.to_string()))],
+        Column::Median => vec![format_samples(timing, |s| {
+            code(format_nanos(s.median_nanos))
+        })],
+        Column::Spread => vec![format_samples(timing, |s| {
+            code(format!("±{}", format_nanos(s.std_dev_nanos)))
+        })],
+        Column::Memory => vec![
+            format_memory(timing.part_1_memory),
+            format_memory(timing.part_2_memory),
+        ],
+        Column::Share => {
+            let share = if total_nanos > 0.0 {
+                timing.total_nanos / total_nanos * 100.0
+            } else {
+                0.0
+            };
+            vec![text(format!("{share:.1}%"))]
+        }
+        Column::Trend => {
+            vec![history::day_sparkline(timings, timing.day).map_or_else(|| text("-"), code)]
+        }
+    }
+}
+
+fn construct_section(
+    machine: Option<Machine>,
+    timings: &Timings,
+    total_millis: f64,
+    config: &TableConfig,
+) -> Section {
+    let columns: Vec<Column> = config
+        .columns
+        .iter()
+        .copied()
+        .filter(|c| is_column_visible(*c, timings))
+        .collect();
+
+    let mut data: Vec<&Timing> = timings.data.iter().collect();
+    if config.sort == Sort::Time {
+        data.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+    }
+
+    let total_nanos = timings.data.iter().map(|t| t.total_nanos).sum();
+
+    Section {
+        machine,
+        headers: columns.iter().flat_map(|c| column_headers(*c)).collect(),
+        rows: data
+            .into_iter()
+            .map(|timing| {
+                columns
+                    .iter()
+                    .flat_map(|c| column_cells(*c, timing, timings, total_nanos, config))
+                    .collect()
+            })
+            .collect(),
+        total_millis,
+    }
+}
+
+fn construct_sections(timings: &Timings, total_millis: f64, config: &TableConfig) -> Vec<Section> {
+    let groups = timings.group_by_machine();
+
+    if groups.len() > 1 {
+        // timings of different machines are not comparable, render one table per machine.
+        groups
+            .into_iter()
+            .map(|(machine, timings)| {
+                let group_millis = timings.total_millis();
+                construct_section(machine, &timings, group_millis, config)
+            })
+            .collect()
+    } else {
+        let (machine, timings) = groups.into_iter().next().unwrap_or_default();
+        vec![construct_section(machine, &timings, total_millis, config)]
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn render_markdown_cell(cell: &Cell) -> String {
+    cell.iter()
+        .map(|span| match span {
+            Span::Text(s) => s.clone(),
+            Span::Code(s) => format!("`{s}`"),
+            Span::Link(s, href) => format!("[{s}]({href})"),
+        })
+        .collect()
+}
+
+fn render_markdown_rows(section: &Section) -> Vec<String> {
+    let join = |cells: Vec<String>| format!("| {} |", cells.join(" | "));
+
+    let mut lines = vec![
+        join(section.headers.iter().map(|h| h.0.to_string()).collect()),
+        join(section.headers.iter().map(|h| h.1.to_string()).collect()),
+    ];
+
+    for row in &section.rows {
+        lines.push(join(row.iter().map(render_markdown_cell).collect()));
+    }
+
+    lines
+}
+
+fn construct_table(
+    prefix: &str,
+    timings: Timings,
+    total_millis: f64,
+    config: &TableConfig,
+) -> String {
     let header = format!("{prefix} Benchmarks");
 
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+
+    let sections = construct_sections(&timings, total_millis, config);
+    let has_headings = sections.len() > 1;
+
+    for section in sections {
+        if has_headings {
+            lines.push(format!(
+                "{prefix}# {}",
+                section
+                    .machine
+                    .as_ref()
+                    .map_or("Unknown machine".into(), Machine::id)
+            ));
+            lines.push(String::new());
+        }
+        if let Some(machine) = &section.machine {
+            lines.push(format!("_{}_", machine.describe()));
+            lines.push(String::new());
+        }
+        lines.extend(render_markdown_rows(&section));
+        lines.push(String::new());
+        lines.push(format!("**Total: {:.2}ms**", section.total_millis));
+        lines.push(String::new());
+    }
+
+    lines.pop();
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn render_html_cell(cell: &Cell) -> String {
+    cell.iter()
+        .map(|span| match span {
+            Span::Text(s) => escape_html(s),
+            Span::Code(s) => format!("<code>{}</code>", escape_html(s)),
+            Span::Link(s, href) => {
+                format!("<a href=\"{}\">{}</a>", escape_html(href), escape_html(s))
+            }
+        })
+        .collect()
+}
+
+fn construct_html(timings: &Timings, total_millis: f64, config: &TableConfig) -> String {
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        "<!DOCTYPE html>".into(),
+        "<html>".into(),
+        "<head>".into(),
+        "<meta charset=\"utf-8\">".into(),
+        "<title>Benchmarks</title>".into(),
+        "<style>body { font-family: sans-serif; } table { border-collapse: collapse; } th, td { border: 1px solid #ccc; padding: 4px 8px; text-align: center; }</style>".into(),
+        "</head>".into(),
+        "<body>".into(),
+        "<h1>Benchmarks</h1>".into(),
     ];
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+    for section in construct_sections(timings, total_millis, config) {
+        if let Some(machine) = &section.machine {
+            lines.push(format!("<h2>{}</h2>", escape_html(&machine.id())));
+            lines.push(format!(
+                "<p><em>{}</em></p>",
+                escape_html(&machine.describe())
+            ));
+        }
+
+        lines.push("<table>".into());
+        lines.push(format!(
+            "<thead><tr>{}</tr></thead>",
+            section
+                .headers
+                .iter()
+                .map(|h| format!("<th>{}</th>", escape_html(h.0)))
+                .collect::<String>()
+        ));
+        lines.push("<tbody>".into());
+        for row in &section.rows {
+            lines.push(format!(
+                "<tr>{}</tr>",
+                row.iter()
+                    .map(|c| format!("<td>{}</td>", render_html_cell(c)))
+                    .collect::<String>()
+            ));
+        }
+        lines.push("</tbody>".into());
+        lines.push("</table>".into());
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            "<p><strong>Total: {:.2}ms</strong></p>",
+            section.total_millis
         ));
     }
 
+    lines.push("</body>".into());
+    lines.push("</html>".into());
     lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+/* -------------------------------------------------------------------------- */
+
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    config: &TableConfig,
+) -> Result<(), Error> {
+    let positions = locate_table(s, MARKER)?;
+    let table = construct_table("##", timings, total_millis, config);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+fn update_markdown(path: &str, timings: Timings, config: &TableConfig) -> Result<(), Error> {
+    let mut content = if Path::new(path).exists() {
+        String::from_utf8_lossy(&fs::read(path)?).to_string()
+    } else {
+        format!("{MARKER}\n{MARKER}\n")
+    };
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
+    update_content(&mut content, timings, total_millis, config)?;
+    fs::write(path, &content)?;
+    Ok(())
+}
+
+/// Write the benchmark table to all targets configured in the environment.
+pub fn update(timings: Timings) -> Result<(), Error> {
+    let config = TableConfig::from_env()?;
+
+    for target in &config.targets {
+        match target {
+            Target::Readme | Target::Benchmarks => {
+                update_markdown(target.path(), timings.clone(), &config)?;
+            }
+            Target::Html => {
+                let total_millis = timings.total_millis();
+                fs::write(
+                    target.path(),
+                    construct_html(&timings, total_millis, &config),
+                )?;
+            }
+        }
+    }
+
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{construct_html, format_bytes, update_content, Column, Sort, TableConfig, MARKER};
+    use crate::{
+        day,
+        template::machine::Machine,
+        template::timings::{MemoryUsage, SampleStats, Timing, Timings},
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -108,21 +617,46 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    parse: None,
                     total_nanos: 3e+10,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    perf: None,
+                    machine: None,
+                    timestamp: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    parse: None,
                     total_nanos: 7e+10,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    perf: None,
+                    machine: None,
+                    timestamp: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    parse: None,
                     total_nanos: 9e+10,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    perf: None,
+                    machine: None,
+                    timestamp: None,
                 },
             ],
+            ..Default::default()
         }
     }
 
@@ -130,28 +664,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableConfig::default()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableConfig::default()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableConfig::default()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableConfig::default()).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableConfig::default()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -159,7 +693,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableConfig::default()).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -179,4 +713,205 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_memory() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_memory = Some(MemoryUsage {
+            total_bytes: 3 * 1024 * 1024,
+            max_bytes: 2048,
+            total_blocks: 42,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableConfig::default()).unwrap();
+
+        assert_eq!(
+            s.contains("| Day | Part 1 | Part 2 | Part 1 Heap | Part 2 Heap |"),
+            true
+        );
+        assert_eq!(
+            s.contains(
+                "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `2.0 KiB` peak, `3.0 MiB` total, 42 allocs | - |"
+            ),
+            true
+        );
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(232), "232 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024 * 1024), "5.0 GiB");
+    }
+
+    #[test]
+    fn format_benchmarks_per_machine() {
+        let machine = |cpu: &str| Machine {
+            cpu: cpu.into(),
+            cores: 8,
+            rustc: "1.83.0".into(),
+            profile: "release".into(),
+            commit: None,
+        };
+
+        let mut timings = get_mock_timings();
+        timings.data[0].machine = Some(machine("Apple M4"));
+        timings.data[1].machine = Some(machine("AMD Ryzen 7"));
+        timings.data[2].machine = Some(machine("Apple M4"));
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableConfig::default()).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "### Apple M4 (8 cores)",
+            "",
+            "_Apple M4 (8 cores), rustc 1.83.0, release profile_",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "",
+            "**Total: 120000.00ms**",
+            "",
+            "### AMD Ryzen 7 (8 cores)",
+            "",
+            "_AMD Ryzen 7 (8 cores), rustc 1.83.0, release profile_",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---:  |",
+            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
+            "",
+            "**Total: 70000.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_with_trend() {
+        let mut timings = get_mock_timings();
+        timings.history = timings.data.clone();
+        let mut faster = timings.data[0].clone();
+        faster.total_nanos = 1e+6;
+        let timings = timings.merge(&Timings {
+            data: vec![faster],
+            ..Default::default()
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableConfig::default()).unwrap();
+
+        assert_eq!(s.contains("| Day | Part 1 | Part 2 | Trend |"), true);
+        assert_eq!(
+            s.contains("| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` | `█▁` |"),
+            true
+        );
+        assert_eq!(
+            s.contains("| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | - |"),
+            true
+        );
+    }
+
+    #[test]
+    fn format_benchmarks_with_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[2].part_1_samples = Some(SampleStats {
+            count: 100,
+            median_nanos: 39_500_000,
+            std_dev_nanos: 1_200_000,
+        });
+        timings.data[2].part_2 = None;
+
+        let config = TableConfig {
+            columns: vec![
+                Column::Day,
+                Column::Stars,
+                Column::Samples,
+                Column::Median,
+                Column::Spread,
+                Column::Share,
+            ],
+            sort: Sort::Time,
+            badges: true,
+            ..Default::default()
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &config).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Stars | Samples | Median | Spread (σ) | Share |",
+            "| :---: | :---: | :---: | :---: | :---: | ---: |",
+            "| [Day 4](./src/bin/04.rs) 🐌 | ⭐ | 100 / - | `39.5ms` / - | `±1.2ms` / - | 47.4% |",
+            "| [Day 2](./src/bin/02.rs) 🐌 | ⭐⭐ | - / - | - / - | - / - | 36.8% |",
+            "| [Day 1](./src/bin/01.rs) 🐌 | ⭐⭐ | - / - | - / - | - / - | 15.8% |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_with_parse_time() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = Some("5ms".into());
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableConfig::default()).unwrap();
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(lines[3], "| Day | Part 1 | Part 2 | Parse |");
+        assert_eq!(
+            lines[6],
+            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` | `5ms` |"
+        );
+        assert_eq!(
+            lines[7],
+            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` | `-` |"
+        );
+    }
+
+    #[test]
+    fn parses_column_config() {
+        let columns: Vec<Column> = ["day", "title", "part_1", "part_2", "trend"]
+            .iter()
+            .map(|x| x.parse().unwrap())
+            .collect();
+        assert_eq!(
+            columns,
+            vec![
+                Column::Day,
+                Column::Title,
+                Column::Part1,
+                Column::Part2,
+                Column::Trend
+            ]
+        );
+        assert_eq!("time".parse::<Sort>().unwrap(), Sort::Time);
+    }
+
+    #[test]
+    fn format_benchmarks_as_html() {
+        let html = construct_html(&get_mock_timings(), 190.0, &TableConfig::default());
+        assert_eq!(
+            html.contains("<thead><tr><th>Day</th><th>Part 1</th><th>Part 2</th></tr></thead>"),
+            true
+        );
+        assert_eq!(
+            html.contains("<tr><td><a href=\"./src/bin/01.rs\">Day 1</a></td><td><code>10ms</code></td><td><code>20ms</code></td></tr>"),
+            true
+        );
+        assert_eq!(
+            html.contains("<p><strong>Total: 190.00ms</strong></p>"),
+            true
+        );
+    }
 }
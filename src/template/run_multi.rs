@@ -3,11 +3,17 @@ use std::{collections::HashSet, io};
 use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
-    all_days,
+    all_days, meta,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    is_memory: bool,
+    is_perf: bool,
+) -> Option<Timings> {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
 
     let mut need_space = false;
@@ -21,21 +27,44 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             }
             need_space = true;
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+            let title = meta::get_title(day).map_or_else(String::new, |t| format!(": {t}"));
+            println!("{ANSI_BOLD}Day {day}{title}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let output = child_commands::run_solution(day, is_timed, is_release, false).unwrap();
 
             if output.is_empty() {
                 println!("Not solved.");
             } else {
-                let val = child_commands::parse_exec_time(&output, day);
+                let mut val = child_commands::parse_exec_time(&output, day);
+
+                if is_memory {
+                    // heap profiling slows down execution, so it runs separately from the timed run.
+                    println!("{ANSI_ITALIC}profiling heap...{ANSI_RESET}");
+                    let output = child_commands::run_solution(day, false, false, true).unwrap();
+                    child_commands::parse_memory(&output, &mut val);
+                }
+
+                if is_perf {
+                    val.perf = child_commands::run_perf(day);
+                    match val.perf {
+                        Some(perf) => println!(
+                            "perf: {} instructions, {} cycles",
+                            perf.instructions, perf.cycles
+                        ),
+                        None => eprintln!("Could not read hardware counters via `perf stat`."),
+                    }
+                }
+
                 timings.push(val);
             }
         });
 
     if is_timed {
-        let timings = Timings { data: timings };
+        let timings = Timings {
+            data: timings,
+            ..Default::default()
+        };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
@@ -67,16 +96,26 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::{
+        timings::{MemoryUsage, PerfCounters, SampleStats},
+        Day,
+    };
     use std::{
+        env,
         io::{BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day.
+    /// `is_dhat` builds the bin with the `dhat` profile and heap profiling enabled, taking precedence over `is_release`.
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        is_dhat: bool,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
@@ -85,7 +124,9 @@ pub mod child_commands {
         let day_padded = day.to_string();
         let mut args = vec!["run", "--quiet", "--bin", &day_padded];
 
-        if is_release {
+        if is_dhat {
+            args.extend(["--profile", "dhat", "--features", "dhat-heap"]);
+        } else if is_release {
             args.push("--release");
         }
 
@@ -127,18 +168,73 @@ pub mod child_commands {
         Ok(output)
     }
 
+    /// Count instructions and cycles of a single, untimed run of the release bin via `perf stat` (linux only).
+    /// Relies on the bin having been built by a preceding release run.
+    pub fn run_perf(day: Day) -> Option<PerfCounters> {
+        let target_dir = env::var("CARGO_TARGET_DIR").unwrap_or_else(|_| "target".into());
+        let bin = Path::new(&target_dir).join("release").join(day.to_string());
+
+        let output = Command::new("perf")
+            .args(["stat", "-x", ",", "-e", "instructions:u,cycles:u", "--"])
+            .arg(bin)
+            .stdout(Stdio::null())
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        parse_perf(&String::from_utf8_lossy(&output.stderr))
+    }
+
+    /// Parse the CSV output of `perf stat -x ,`, e.g. `1234,,instructions:u,5678,100.00,,`.
+    fn parse_perf(output: &str) -> Option<PerfCounters> {
+        let mut instructions = None;
+        let mut cycles = None;
+
+        for line in output.lines() {
+            let fields: Vec<&str> = line.split(',').collect();
+            let (Some(value), Some(event)) = (fields.first(), fields.get(2)) else {
+                continue;
+            };
+
+            // NOTE: unsupported events are reported as `<not supported>` and fail to parse.
+            let value = value.parse::<u64>().ok();
+
+            if event.starts_with("instructions") {
+                instructions = value;
+            } else if event.starts_with("cycles") {
+                cycles = value;
+            }
+        }
+
+        Some(PerfCounters {
+            instructions: instructions?,
+            cycles: cycles?,
+        })
+    }
+
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
             part_1: None,
             part_2: None,
+            parse: None,
             total_nanos: 0_f64,
+            part_1_memory: None,
+            part_2_memory: None,
+            part_1_samples: None,
+            part_2_samples: None,
+            perf: None,
+            machine: None,
+            timestamp: None,
         };
 
         output
             .iter()
             .filter_map(|l| {
-                if !l.contains(" samples)") {
+                if !l.contains(" samples") {
                     return None;
                 }
 
@@ -148,13 +244,29 @@ pub mod child_commands {
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                Some((
+                    part,
+                    timing_str,
+                    nanos,
+                    parse_samples(l),
+                    parse_parse_time(l),
+                ))
             })
-            .for_each(|(part, timing_str, nanos)| {
+            .for_each(|(part, timing_str, nanos, samples, parse)| {
                 if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
+                    timings.part_1_samples = samples;
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
+                    timings.part_2_samples = samples;
+                }
+
+                // parts of a split measurement share their input, count its parse time once.
+                if let Some((parse_str, parse_nanos)) = parse {
+                    if timings.parse.is_none() {
+                        timings.parse = Some(parse_str.into());
+                        timings.total_nanos += parse_nanos;
+                    }
                 }
 
                 timings.total_nanos += nanos;
@@ -163,29 +275,102 @@ pub mod child_commands {
         timings
     }
 
+    /// Parse heap usage lines printed by the runner, e.g. `Part 1 (heap): 276 bytes in 3 blocks, peak 232 bytes`.
+    /// A `Parse (heap)` line precedes the parts sharing that parsed input and is added to each of them.
+    pub fn parse_memory(output: &[String], timing: &mut super::Timing) {
+        let mut parse: Option<MemoryUsage> = None;
+
+        for line in output {
+            let Some((part, stats)) = line.split_once(" (heap): ") else {
+                continue;
+            };
+
+            let numbers: Vec<u64> = stats
+                .split(|c: char| !c.is_ascii_digit())
+                .filter_map(|x| x.parse().ok())
+                .collect();
+
+            let [total_bytes, total_blocks, max_bytes] = numbers[..] else {
+                eprintln!("Could not parse heap usage from line: {line}");
+                continue;
+            };
+
+            let mut memory = MemoryUsage {
+                total_bytes,
+                max_bytes,
+                total_blocks,
+            };
+
+            if part == "Parse" {
+                parse = Some(memory);
+                continue;
+            }
+
+            // NOTE: the parsed input may not live through the whole part, so the summed peak is an upper bound.
+            if let Some(parse) = parse {
+                memory.total_bytes += parse.total_bytes;
+                memory.max_bytes += parse.max_bytes;
+                memory.total_blocks += parse.total_blocks;
+            }
+
+            let memory = Some(memory);
+
+            if part == "Part 1" {
+                timing.part_1_memory = memory;
+            } else if part == "Part 2" {
+                timing.part_2_memory = memory;
+            }
+        }
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
+    fn parse_duration(s: &str) -> Option<f64> {
         // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+        match s {
+            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
+            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+        }
+    }
+
+    fn parse_time(line: &str) -> Option<(&str, f64)> {
         let str_timing = line
-            .split(" samples)")
+            .split(" samples")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
-            .next()?
-            .trim();
+            .next()?;
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        // split measurements, e.g. `(parse 120.0µs + solve 30.0µs @ 5000 samples)`, report the solve phase as part time.
+        let str_timing = str_timing.rsplit("solve ").next()?.trim();
+
+        Some((str_timing, parse_duration(str_timing)?))
+    }
 
-        Some((str_timing, parsed_timing))
+    /// Parse the input-preparation phase of a split measurement, e.g. `(parse 120.0µs + solve 30.0µs @ 5000 samples)`.
+    fn parse_parse_time(line: &str) -> Option<(&str, f64)> {
+        let str_timing = line.split("(parse ").nth(1)?.split(" + ").next()?.trim();
+        Some((str_timing, parse_duration(str_timing)?))
+    }
+
+    /// Parse sample statistics of a benched part, e.g. `(28.2ms @ 100 samples, median 27.9ms, σ 1.2ms)`.
+    fn parse_samples(line: &str) -> Option<SampleStats> {
+        let (head, tail) = line.rsplit_once(" samples")?;
+        let count = head.rsplit('@').next()?.trim().parse().ok()?;
+        let median = tail.split("median ").nth(1)?.split(',').next()?.trim();
+        let std_dev = tail.split("σ ").nth(1)?.split(')').next()?.trim();
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Some(SampleStats {
+            count,
+            median_nanos: parse_duration(median)? as u64,
+            std_dev_nanos: parse_duration(std_dev)? as u64,
+        })
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -204,7 +389,7 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_exec_time, parse_memory, parse_perf};
 
         use crate::day;
 
@@ -238,6 +423,50 @@ pub mod child_commands {
             assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
+        #[test]
+        fn parses_sample_statistics() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (28.2ms @ 100 samples, median 27.9ms, σ 1.2ms)".into(),
+                    "Part 2: 10 (74.13ns @ 10000 samples, median 70.0ns, σ 2.5µs)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 28200074.13_f64);
+            assert_eq!(res.part_1.unwrap(), "28.2ms");
+            assert_eq!(res.part_2.unwrap(), "74.13ns");
+
+            let samples = res.part_1_samples.unwrap();
+            assert_eq!(samples.count, 100);
+            assert_eq!(samples.median_nanos, 27_900_000);
+            assert_eq!(samples.std_dev_nanos, 1_200_000);
+
+            let samples = res.part_2_samples.unwrap();
+            assert_eq!(samples.count, 10000);
+            assert_eq!(samples.median_nanos, 70);
+            assert_eq!(samples.std_dev_nanos, 2500);
+        }
+
+        #[test]
+        fn parses_split_measurements() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (parse 1.5ms + solve 28.2ms @ 100 samples, median 27.9ms, σ 1.2ms)"
+                        .into(),
+                    "Part 2: 10 (parse 1.5ms + solve 1.3ms @ 100 samples, median 1.2ms, σ 0.1ms)"
+                        .into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 31000000_f64);
+            assert_eq!(res.parse.unwrap(), "1.5ms");
+            assert_eq!(res.part_1.unwrap(), "28.2ms");
+            assert_eq!(res.part_2.unwrap(), "1.3ms");
+            assert_eq!(res.part_1_samples.unwrap().median_nanos, 27_900_000);
+        }
+
         #[test]
         fn parses_missing_parts() {
             let res = parse_exec_time(
@@ -252,5 +481,59 @@ pub mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn parses_heap_usage() {
+            let mut res = parse_exec_time(&["Part 1: 0 (74.13ns)".into()], day!(1));
+            parse_memory(
+                &[
+                    "Part 1: 0 (74.13ns)".into(),
+                    "Part 1 (heap): 276 bytes in 3 blocks, peak 232 bytes".into(),
+                    "Part 2: ✖        ".into(),
+                ],
+                &mut res,
+            );
+            let memory = res.part_1_memory.unwrap();
+            assert_eq!(memory.total_bytes, 276);
+            assert_eq!(memory.total_blocks, 3);
+            assert_eq!(memory.max_bytes, 232);
+            assert_eq!(res.part_2_memory.is_none(), true);
+        }
+
+        #[test]
+        fn adds_parse_heap_usage_to_parts() {
+            let mut res = parse_exec_time(&[], day!(1));
+            parse_memory(
+                &[
+                    "Parse (heap): 1000 bytes in 10 blocks, peak 800 bytes".into(),
+                    "Part 1 (heap): 276 bytes in 3 blocks, peak 232 bytes".into(),
+                    "Part 2 (heap): 0 bytes in 0 blocks, peak 0 bytes".into(),
+                ],
+                &mut res,
+            );
+            let memory = res.part_1_memory.unwrap();
+            assert_eq!(memory.total_bytes, 1276);
+            assert_eq!(memory.total_blocks, 13);
+            assert_eq!(memory.max_bytes, 1032);
+            assert_eq!(res.part_2_memory.unwrap().total_bytes, 1000);
+        }
+
+        #[test]
+        fn parses_perf_counters() {
+            let res = parse_perf(
+                "1234567,,instructions:u,812345,100.00,,\n7654321,,cycles:u,812345,100.00,,\n",
+            )
+            .unwrap();
+            assert_eq!(res.instructions, 1234567);
+            assert_eq!(res.cycles, 7654321);
+        }
+
+        #[test]
+        fn parses_unsupported_perf_counters() {
+            let res = parse_perf(
+                "<not supported>,,instructions:u,0,100.00,,\n<not supported>,,cycles:u,0,100.00,,\n",
+            );
+            assert_eq!(res.is_none(), true);
+        }
     }
 }
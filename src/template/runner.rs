@@ -6,65 +6,140 @@ use std::process::Output;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use crate::template::timings::MemoryUsage;
 use crate::template::ANSI_BOLD;
 use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
 
+/// Run a solution part on its own copy of the input. Copying the input is reported as the input-preparation phase.
 pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+    let (_, prepare, _) = run_prepare(|input: &I| input.clone(), &input);
+    run_part_split(func, input, &prepare, day, part);
+}
+
+/// Parse the input of a [`Solution`](crate::template::Solution) once. The returned samples are reported as the
+/// input-preparation phase of every part, see [`run_part_parsed`].
+pub fn run_parse<P>(func: impl Fn(&str) -> P, input: &str) -> (P, Samples) {
+    let (parsed, samples, memory) = run_prepare(func, input);
+
+    if let Some(memory) = memory {
+        print_memory(&memory, "Parse");
+    }
+
+    (parsed, samples)
+}
+
+fn run_prepare<I: Clone, P>(func: impl Fn(I) -> P, input: I) -> (P, Samples, Option<MemoryUsage>) {
+    let result = run_timed(func, input, |_| print!("Preparing input..."));
+
+    // clear the intermediate output, the preparation time is printed together with the parts.
+    print!("\r\x1b[2K");
+    let _ = stdout().flush();
+
+    result
+}
+
+/// Run a solution part on a parsed input, printing parse and solve time separately.
+pub fn run_part_parsed<P, T: Display>(
+    func: impl Fn(&P) -> Option<T>,
+    parsed: &P,
+    parse: &Samples,
+    day: Day,
+    part: u8,
+) {
+    run_part_split(func, parsed, parse, day, part);
+}
+
+fn run_part_split<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    parse: &Samples,
+    day: Day,
+    part: u8,
+) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
+    let (result, samples, memory) =
         run_timed(func, input, |result| print_result(result, &part_str, ""));
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(&result, &part_str, &format_duration(&samples, parse));
+
+    if let Some(memory) = memory {
+        print_memory(&memory, &part_str);
+    }
 
     if let Some(result) = result {
         submit_result(result, day, part);
     }
 }
 
+/// Summary of the execution times of a solution part.
+pub struct Samples {
+    mean: Duration,
+    median: Duration,
+    std_dev: Duration,
+    count: u128,
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// When built with the `dhat-heap` feature, heap usage of the first execution is returned as well.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Samples, Option<MemoryUsage>) {
+    // the copy handed to the first run is prepared before the timer starts, like the benched runs.
+    let cloned = input.clone();
     let timer = Instant::now();
-    let result = {
-        let input = input.clone();
-
+    let (result, memory) = {
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        let result = func(cloned);
+
+        #[cfg(feature = "dhat-heap")]
+        let memory = {
+            let stats = dhat::HeapStats::get();
+            Some(MemoryUsage {
+                total_bytes: stats.total_bytes,
+                max_bytes: stats.max_bytes as u64,
+                total_blocks: stats.total_blocks,
+            })
+        };
+
+        #[cfg(not(feature = "dhat-heap"))]
+        let memory = None;
+
+        (result, memory)
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let samples = if std::env::args().any(|x| x == "--time") {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        Samples {
+            mean: base_time,
+            median: base_time,
+            std_dev: Duration::ZERO,
+            count: 1,
+        }
     };
 
-    (result, run.0, run.1)
+    (result, samples, memory)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Samples {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -76,11 +151,15 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    timers.sort_unstable();
+
+    #[allow(clippy::cast_possible_truncation)]
+    Samples {
+        mean: Duration::from_nanos(average_duration(&timers) as u64),
+        median: timers[timers.len() / 2],
+        std_dev: Duration::from_nanos(std_dev_duration(&timers) as u64),
+        count: bench_iterations,
+    }
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
@@ -91,14 +170,44 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+#[allow(clippy::cast_precision_loss)]
+fn std_dev_duration(numbers: &[Duration]) -> f64 {
+    let mean = average_duration(numbers) as f64;
+    let variance = numbers
+        .iter()
+        .map(|x| (x.as_nanos() as f64 - mean).powi(2))
+        .sum::<f64>()
+        / numbers.len() as f64;
+    variance.sqrt()
+}
+
+/// Formats the execution time of a part, e.g. ` (parse 120.0µs + solve 30.0µs @ 5000 samples, median 29.8µs, σ 1.2µs)`.
+/// Median and standard deviation refer to the solve phase.
+fn format_duration(samples: &Samples, parse: &Samples) -> String {
+    let Samples {
+        mean,
+        median,
+        std_dev,
+        count,
+    } = samples;
+
+    let time = format!("parse {:.1?} + solve {mean:.1?}", parse.mean);
+
+    if *count == 1 {
+        format!(" ({time})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({time} @ {count} samples, median {median:.1?}, σ {std_dev:.1?})")
     }
 }
 
+/// Prints heap usage in a format that `run_multi` can parse, e.g. `Part 1 (heap): 276 bytes in 3 blocks, peak 232 bytes`.
+fn print_memory(memory: &MemoryUsage, part: &str) {
+    println!(
+        "{part} (heap): {} bytes in {} blocks, peak {} bytes",
+        memory.total_bytes, memory.total_blocks, memory.max_bytes
+    );
+}
+
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
@@ -148,6 +257,11 @@ fn submit_result<T: Display>(
         return None;
     }
 
+    if args.contains(&"--example".into()) {
+        eprintln!("Not submitting, the solution ran on the example.");
+        return None;
+    }
+
     if args.len() < 3 {
         eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
         process::exit(1);
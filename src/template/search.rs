@@ -0,0 +1,165 @@
+/// Module that searches past days for a technique: the puzzle descriptions in `data/puzzles`,
+/// the solutions in `src/bin` and the topic tags of each day.
+///
+/// Tags are read from the day's metadata and from a `// tags: grid, dijkstra` comment in the solution file.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::template::meta::Meta;
+use crate::template::{all_days, Day};
+
+/// A line that contains the search term.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LineMatch {
+    pub line_number: usize,
+    pub line: String,
+}
+
+/// Matches of a single file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileMatch {
+    pub path: PathBuf,
+    pub lines: Vec<LineMatch>,
+}
+
+/// A day that matched the query.
+#[derive(Clone, Debug)]
+pub struct DayMatch {
+    pub day: Day,
+    pub title: Option<String>,
+    pub tags: Vec<String>,
+    /// The term equals one of the day's tags.
+    pub tag_match: bool,
+    pub files: Vec<FileMatch>,
+}
+
+/// Searches all days for `term` and keeps only days tagged with `tag`, if given.
+#[derive(Clone, Debug, Default)]
+pub struct Query {
+    pub term: Option<String>,
+    pub tag: Option<String>,
+}
+
+fn is_tag(tags: &[String], tag: &str) -> bool {
+    tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+}
+
+/// Reads the tags declared in a solution file, e.g. `// tags: grid, dijkstra`.
+pub fn parse_source_tags(source: &str) -> Vec<String> {
+    source
+        .lines()
+        .filter_map(|l| l.trim().strip_prefix("// tags:"))
+        .flat_map(|tags| tags.split(','))
+        .map(|tag| tag.trim().to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect()
+}
+
+/// Finds the lines of `text` that contain `term`, ignoring case.
+pub fn find_lines(text: &str, term: &str) -> Vec<LineMatch> {
+    let term = term.to_lowercase();
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| line.to_lowercase().contains(&term))
+        .map(|(i, line)| LineMatch {
+            line_number: i + 1,
+            line: line.trim().to_string(),
+        })
+        .collect()
+}
+
+fn search_file(path: &Path, term: &str) -> Option<FileMatch> {
+    let text = fs::read_to_string(path).ok()?;
+    let lines = find_lines(&text, term);
+    if lines.is_empty() {
+        None
+    } else {
+        Some(FileMatch {
+            path: path.to_path_buf(),
+            lines,
+        })
+    }
+}
+
+fn search_day(day: Day, query: &Query) -> Option<DayMatch> {
+    let source_path = PathBuf::from(format!("src/bin/{day}.rs"));
+    let puzzle_path = PathBuf::from(format!("data/puzzles/{day}.md"));
+
+    let source = fs::read_to_string(&source_path).ok();
+    if source.is_none() && !puzzle_path.exists() {
+        return None;
+    }
+
+    let meta = Meta::read_or_default(day);
+    let mut tags = meta.tags;
+    for tag in parse_source_tags(source.as_deref().unwrap_or_default()) {
+        if !is_tag(&tags, &tag) {
+            tags.push(tag);
+        }
+    }
+    tags.sort();
+
+    if let Some(tag) = &query.tag {
+        if !is_tag(&tags, tag) {
+            return None;
+        }
+    }
+
+    let (tag_match, files) = match &query.term {
+        Some(term) => (
+            is_tag(&tags, term),
+            [puzzle_path, source_path]
+                .iter()
+                .filter_map(|path| search_file(path, term))
+                .collect(),
+        ),
+        None => (false, vec![]),
+    };
+
+    if query.term.is_some() && !tag_match && files.is_empty() {
+        return None;
+    }
+
+    Some(DayMatch {
+        day,
+        title: meta.title,
+        tags,
+        tag_match,
+        files,
+    })
+}
+
+/// Searches all days, days with a tag match first.
+pub fn search(query: &Query) -> Vec<DayMatch> {
+    let mut matches: Vec<DayMatch> = all_days()
+        .filter_map(|day| search_day(day, query))
+        .collect();
+    matches.sort_by_key(|m| (!m.tag_match, m.day));
+    matches
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find_lines, parse_source_tags};
+
+    #[test]
+    fn parses_source_tags() {
+        let source = "advent_of_code::solution!(17);\n// tags: Grid, dijkstra,\nfn main() {}";
+        assert_eq!(parse_source_tags(source), vec!["grid", "dijkstra"]);
+        assert_eq!(parse_source_tags("fn main() {}").len(), 0);
+    }
+
+    #[test]
+    fn finds_lines_ignoring_case() {
+        let text =
+            "use std::collections::BinaryHeap;\n\nfn dijkstra() {}\n// Dijkstra on (cell, dir)";
+        let lines = find_lines(text, "DIJKSTRA");
+        assert_eq!(
+            lines.iter().map(|l| l.line_number).collect::<Vec<_>>(),
+            vec![3, 4]
+        );
+        assert_eq!(lines[1].line, "// Dijkstra on (cell, dir)");
+    }
+}
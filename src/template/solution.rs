@@ -0,0 +1,17 @@
+/// Optional trait for solutions that share a parsed input between both parts.
+use std::fmt::Display;
+
+/// A solution that parses its input once and solves both parts from the parsed representation.
+///
+/// Wire it up with `solution!(DAY, Type)`. The input is parsed once and each part reports the shared parse time next to
+/// its own solve time, e.g. `Part 1: 46 (parse 19.4µs + solve 11.0µs)`.
+pub trait Solution {
+    /// Parsed representation of the puzzle input.
+    type Parsed;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Self::Parsed;
+    fn part_one(parsed: &Self::Parsed) -> Option<Self::Answer1>;
+    fn part_two(parsed: &Self::Parsed) -> Option<Self::Answer2>;
+}
@@ -1,24 +1,71 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{machine::Machine, Day};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
+/// Represents heap usage of a single solution part, as reported by dhat.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// Number of bytes allocated over the entire run.
+    pub total_bytes: u64,
+    /// Number of bytes allocated at the heap peak.
+    pub max_bytes: u64,
+    /// Number of allocations over the entire run.
+    pub total_blocks: u64,
+}
+
+/// Represents the distribution of benchmark samples of a single solution part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SampleStats {
+    pub count: u64,
+    pub median_nanos: u64,
+    pub std_dev_nanos: u64,
+}
+
+/// Represents hardware counters of a single run of a solution binary, as reported by `perf stat`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PerfCounters {
+    pub instructions: u64,
+    pub cycles: u64,
+}
+
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    /// Time spent parsing the input, for solutions that share it between parts.
+    /// Part times then only cover the solve phase, the total includes parsing once.
+    pub parse: Option<String>,
     pub total_nanos: f64,
+    pub part_1_samples: Option<SampleStats>,
+    pub part_2_samples: Option<SampleStats>,
+    pub part_1_memory: Option<MemoryUsage>,
+    pub part_2_memory: Option<MemoryUsage>,
+    pub perf: Option<PerfCounters>,
+    pub machine: Option<Machine>,
+    /// Unix timestamp (seconds) of when the timing was recorded.
+    pub timestamp: Option<u64>,
+}
+
+impl Timing {
+    /// Id of the machine this timing was recorded on, see [`Machine::id`].
+    pub fn machine_id(&self) -> Option<String> {
+        self.machine.as_ref().map(Machine::id)
+    }
 }
 
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
+    /// Latest timing per day and machine.
     pub data: Vec<Timing>,
+    /// Every timing ever merged, in order of recording.
+    pub history: Vec<Timing>,
 }
 
 impl Timings {
@@ -45,6 +92,7 @@ impl Timings {
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Timings are only overwritten by timings of the same machine, and all of `other` is appended to the history.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
@@ -53,13 +101,21 @@ impl Timings {
         }
 
         for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
+            let machine_id = timing.machine_id();
+            if !data
+                .iter()
+                .any(|t| t.day == timing.day && t.machine_id() == machine_id)
+            {
                 data.push(timing.clone());
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        data.sort_unstable_by_key(|a| a.day);
+
+        let mut history = self.history.clone();
+        history.extend(new.data.iter().cloned());
+
+        Timings { data, history }
     }
 
     /// Sum up total duration of timings as millis.
@@ -67,11 +123,62 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    /// Timings recorded on the machine with the given id, see [`Machine::id`].
+    pub fn for_machine(&self, machine_id: Option<&str>) -> Self {
+        let filter = |timings: &[Timing]| {
+            timings
+                .iter()
+                .filter(|t| t.machine_id().as_deref() == machine_id)
+                .cloned()
+                .collect()
+        };
+
+        Timings {
+            data: filter(&self.data),
+            history: filter(&self.history),
+        }
+    }
+
+    /// Split timings into one set per machine, in order of first appearance.
+    pub fn group_by_machine(&self) -> Vec<(Option<Machine>, Timings)> {
+        let mut machines: Vec<Option<Machine>> = vec![];
+
+        for timing in &self.data {
+            let machine_id = timing.machine_id();
+            if !machines
+                .iter()
+                .any(|m| m.as_ref().map(Machine::id) == machine_id)
+            {
+                machines.push(timing.machine.clone());
+            }
+        }
+
+        machines
+            .into_iter()
+            .map(|m| {
+                let timings = self.for_machine(m.as_ref().map(Machine::id).as_deref());
+                (m, timings)
+            })
+            .collect()
+    }
+
+    /// Recorded timings of a single day, oldest first.
+    pub fn day_history(&self, day: Day) -> Vec<&Timing> {
+        self.history.iter().filter(|t| t.day == day).collect()
+    }
+
     pub fn is_day_complete(&self, day: Day) -> bool {
         self.data
             .iter()
             .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
     }
+
+    /// Whether any of the timings carries heap usage information.
+    pub fn has_memory(&self) -> bool {
+        self.data
+            .iter()
+            .any(|t| t.part_1_memory.is_some() || t.part_2_memory.is_some())
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -85,6 +192,11 @@ impl From<Timings> for JsonValue {
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
 
+        map.insert(
+            "history".into(),
+            JsonValue::Array(value.history.iter().map(JsonValue::from).collect()),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -95,20 +207,33 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
-        Ok(Timings {
-            data: json_data
+        let data: Vec<Timing> = json_data
+            .iter()
+            .map(Timing::try_from)
+            .collect::<Result<_, _>>()?;
+
+        // NOTE: timings stored before the history existed start it off with their latest values.
+        let history = match json.get("history") {
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected `json.history` to be an array.")?
                 .iter()
                 .map(Timing::try_from)
                 .collect::<Result<_, _>>()?,
-        })
+            None => data.clone(),
+        };
+
+        Ok(Timings { data, history })
     }
 }
 
@@ -140,6 +265,43 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "parse".into(),
+            match &value.parse {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert(
+            "part_1_samples".into(),
+            samples_to_json(value.part_1_samples),
+        );
+        map.insert(
+            "part_2_samples".into(),
+            samples_to_json(value.part_2_samples),
+        );
+        map.insert("part_1_memory".into(), memory_to_json(value.part_1_memory));
+        map.insert("part_2_memory".into(), memory_to_json(value.part_2_memory));
+        map.insert("perf".into(), perf_to_json(value.perf));
+
+        map.insert(
+            "machine".into(),
+            match &value.machine {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            match value.timestamp {
+                Some(x) => JsonValue::Number(x as f64),
+                None => JsonValue::Null,
+            },
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -168,22 +330,164 @@ impl TryFrom<&JsonValue> for Timing {
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
             .ok_or("Expected timing.part_2 to be null or string.")?;
 
+        // NOTE: optional, timings stored before split measurements existed lack it.
+        let parse = match json.get("parse") {
+            Some(v) if !v.is_null() => Some(
+                v.get::<String>()
+                    .cloned()
+                    .ok_or("Expected timing.parse to be null or string.")?,
+            ),
+            _ => None,
+        };
+
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        let part_1_samples = samples_from_json(json.get("part_1_samples"))
+            .ok_or("Expected timing.part_1_samples to be null or an object.")?;
+
+        let part_2_samples = samples_from_json(json.get("part_2_samples"))
+            .ok_or("Expected timing.part_2_samples to be null or an object.")?;
+
+        // NOTE: memory keys are optional, timings stored before `--memory` existed lack them.
+        let part_1_memory = memory_from_json(json.get("part_1_memory"))
+            .ok_or("Expected timing.part_1_memory to be null or an object.")?;
+
+        let part_2_memory = memory_from_json(json.get("part_2_memory"))
+            .ok_or("Expected timing.part_2_memory to be null or an object.")?;
+
+        let perf = perf_from_json(json.get("perf"))
+            .ok_or("Expected timing.perf to be null or an object.")?;
+
+        let machine = match json.get("machine") {
+            Some(v) if !v.is_null() => Some(Machine::try_from(v)?),
+            _ => None,
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = match json.get("timestamp") {
+            Some(v) if !v.is_null() => Some(
+                v.get::<f64>()
+                    .map(|x| *x as u64)
+                    .ok_or("Expected timing.timestamp to be null or a number.")?,
+            ),
+            _ => None,
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            parse,
             total_nanos,
+            part_1_samples,
+            part_2_samples,
+            part_1_memory,
+            part_2_memory,
+            perf,
+            machine,
+            timestamp,
         })
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
+/// Serializes a set of named counters to a JSON object.
+fn counters_to_json(counters: &[(&str, u64)]) -> JsonValue {
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+    for (key, value) in counters {
+        #[allow(clippy::cast_precision_loss)]
+        map.insert((*key).into(), JsonValue::Number(*value as f64));
+    }
+
+    JsonValue::Object(map)
+}
+
+/// Deserializes a set of named counters from a JSON object.
+/// Returns `None` if the value is malformed, `Some(None)` if it is absent or null.
+fn counters_from_json<const N: usize>(
+    value: Option<&JsonValue>,
+    keys: [&str; N],
+) -> Option<Option<[u64; N]>> {
+    let Some(value) = value.filter(|v| !v.is_null()) else {
+        return Some(None);
+    };
+
+    let json = value.get::<HashMap<String, JsonValue>>()?;
+    let mut counters = [0; N];
+
+    for (counter, key) in counters.iter_mut().zip(keys) {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        {
+            *counter = *json.get(key)?.get::<f64>()? as u64;
+        }
+    }
+
+    Some(Some(counters))
+}
+
+fn samples_to_json(value: Option<SampleStats>) -> JsonValue {
+    value.map_or(JsonValue::Null, |s| {
+        counters_to_json(&[
+            ("count", s.count),
+            ("median_nanos", s.median_nanos),
+            ("std_dev_nanos", s.std_dev_nanos),
+        ])
+    })
+}
+
+fn samples_from_json(value: Option<&JsonValue>) -> Option<Option<SampleStats>> {
+    let counters = counters_from_json(value, ["count", "median_nanos", "std_dev_nanos"])?;
+    Some(
+        counters.map(|[count, median_nanos, std_dev_nanos]| SampleStats {
+            count,
+            median_nanos,
+            std_dev_nanos,
+        }),
+    )
+}
+
+fn memory_to_json(value: Option<MemoryUsage>) -> JsonValue {
+    value.map_or(JsonValue::Null, |m| {
+        counters_to_json(&[
+            ("total_bytes", m.total_bytes),
+            ("max_bytes", m.max_bytes),
+            ("total_blocks", m.total_blocks),
+        ])
+    })
+}
+
+fn memory_from_json(value: Option<&JsonValue>) -> Option<Option<MemoryUsage>> {
+    let counters = counters_from_json(value, ["total_bytes", "max_bytes", "total_blocks"])?;
+    Some(
+        counters.map(|[total_bytes, max_bytes, total_blocks]| MemoryUsage {
+            total_bytes,
+            max_bytes,
+            total_blocks,
+        }),
+    )
+}
+
+fn perf_to_json(value: Option<PerfCounters>) -> JsonValue {
+    value.map_or(JsonValue::Null, |p| {
+        counters_to_json(&[("instructions", p.instructions), ("cycles", p.cycles)])
+    })
+}
+
+fn perf_from_json(value: Option<&JsonValue>) -> Option<Option<PerfCounters>> {
+    let counters = counters_from_json(value, ["instructions", "cycles"])?;
+    Some(counters.map(|[instructions, cycles]| PerfCounters {
+        instructions,
+        cycles,
+    }))
+}
+
+/* -------------------------------------------------------------------------- */
+
 #[cfg(feature = "test_lib")]
 mod tests {
     use crate::day;
@@ -197,26 +501,54 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    parse: None,
                     total_nanos: 3e+10,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    perf: None,
+                    machine: None,
+                    timestamp: None,
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    parse: None,
                     total_nanos: 7e+10,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    perf: None,
+                    machine: None,
+                    timestamp: None,
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    parse: None,
                     total_nanos: 4e+10,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    perf: None,
+                    machine: None,
+                    timestamp: None,
                 },
             ],
+            ..Default::default()
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::timings::{MemoryUsage, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
@@ -230,6 +562,22 @@ mod tests {
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_memory() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": "2ms", "total_nanos": 3000000, "part_1_memory": { "total_bytes": 276, "max_bytes": 232, "total_blocks": 3 }, "part_2_memory": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(
+                timing.part_1_memory,
+                Some(MemoryUsage {
+                    total_bytes: 276,
+                    max_bytes: 232,
+                    total_blocks: 3
+                })
+            );
+            assert_eq!(timing.part_2_memory, None);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -288,11 +636,20 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    parse: None,
                     total_nanos: 3_000_000_000_f64,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    perf: None,
+                    machine: None,
+                    timestamp: None,
                 }],
+                ..Default::default()
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -302,11 +659,20 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    parse: None,
                     total_nanos: 1_000_000_000_f64,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    perf: None,
+                    machine: None,
+                    timestamp: None,
                 }],
+                ..Default::default()
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -316,17 +682,27 @@ mod tests {
                     day: day!(1),
                     part_1: None,
                     part_2: None,
+                    parse: None,
                     total_nanos: 0.0,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    perf: None,
+                    machine: None,
+                    timestamp: None,
                 }],
+                ..Default::default()
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
+            template::machine::Machine,
             template::timings::{Timing, Timings},
         };
 
@@ -340,8 +716,17 @@ mod tests {
                     day: day!(3),
                     part_1: None,
                     part_2: None,
+                    parse: None,
                     total_nanos: 0_f64,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    perf: None,
+                    machine: None,
+                    timestamp: None,
                 }],
+                ..Default::default()
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -360,8 +745,17 @@ mod tests {
                     day: day!(2),
                     part_1: None,
                     part_2: None,
+                    parse: None,
                     total_nanos: 0_f64,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    part_1_samples: None,
+                    part_2_samples: None,
+                    perf: None,
+                    machine: None,
+                    timestamp: None,
                 }],
+                ..Default::default()
             };
             let merged = timings.merge(&other);
 
@@ -387,5 +781,34 @@ mod tests {
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 3);
         }
+
+        #[test]
+        fn keeps_timings_of_other_machines() {
+            let timings = get_mock_timings();
+
+            let mut timing = timings.data[1].clone();
+            timing.total_nanos = 0_f64;
+            timing.machine = Some(Machine {
+                cpu: "Apple M4".into(),
+                cores: 10,
+                rustc: "1.83.0".into(),
+                profile: "release".into(),
+                commit: None,
+            });
+
+            let other = Timings {
+                data: vec![timing],
+                ..Default::default()
+            };
+            let merged = timings.merge(&other);
+
+            assert_eq!(merged.data.len(), 4);
+            assert_eq!(merged.for_machine(None).data.len(), 3);
+            assert_eq!(
+                merged.for_machine(Some("Apple M4 (10 cores)")).data[0].total_nanos,
+                0_f64
+            );
+            assert_eq!(merged.group_by_machine().len(), 2);
+        }
     }
 }
@@ -0,0 +1,205 @@
+/// Module that watches the files of a day for changes, used by `cargo solve <day> --watch`.
+/// Changes are detected by polling modification times, which works the same on every platform.
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::template::Day;
+
+/// Modification times of the watched files.
+pub type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+fn collect_files(path: &Path, files: &mut Vec<PathBuf>) {
+    if path.is_dir() {
+        let Ok(entries) = fs::read_dir(path) else {
+            return;
+        };
+        for entry in entries.filter_map(Result::ok) {
+            collect_files(&entry.path(), files);
+        }
+    } else if path.is_file() {
+        files.push(path.to_path_buf());
+    }
+}
+
+/// The day's solution, the shared `src/utils` modules and the day's example and input files.
+pub fn watched_files(day: Day) -> Vec<PathBuf> {
+    let mut files = vec![];
+
+    collect_files(&PathBuf::from(format!("src/bin/{day}.rs")), &mut files);
+    collect_files(Path::new("src/utils"), &mut files);
+    collect_files(&PathBuf::from(format!("data/inputs/{day}.txt")), &mut files);
+
+    // examples may be split per part, e.g. `01-1.txt` and `01-2.txt`.
+    if let Ok(entries) = fs::read_dir(Path::new("data").join("examples")) {
+        for entry in entries.filter_map(Result::ok) {
+            if entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with(&day.to_string())
+            {
+                collect_files(&entry.path(), &mut files);
+            }
+        }
+    }
+
+    files
+}
+
+pub fn snapshot(day: Day) -> Snapshot {
+    watched_files(day)
+        .into_iter()
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, modified))
+        })
+        .collect()
+}
+
+/// Files that were added, modified or removed between two snapshots.
+pub fn changed_files(previous: &Snapshot, current: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = current
+        .iter()
+        .filter(|(path, modified)| previous.get(*path) != Some(modified))
+        .map(|(path, _)| path.clone())
+        .collect();
+
+    changed.extend(
+        previous
+            .keys()
+            .filter(|path| !current.contains_key(*path))
+            .cloned(),
+    );
+
+    changed
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Outcome of `cargo test --bin <day>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TestOutcome {
+    CompileError(String),
+    Finished { passed: usize, failed: Vec<String> },
+}
+
+/// Reads passed and failed tests from the output of `cargo test`.
+/// A run without a `test result` line did not compile.
+pub fn parse_test_output(stdout: &str, stderr: &str) -> TestOutcome {
+    let Some(summary) = stdout.lines().find(|l| l.starts_with("test result:")) else {
+        let error: Vec<&str> = stderr
+            .lines()
+            .skip_while(|l| !l.starts_with("error"))
+            .take(12)
+            .collect();
+        return TestOutcome::CompileError(error.join("\n"));
+    };
+
+    let passed = summary
+        .split(". ")
+        .nth(1)
+        .and_then(|s| s.split(' ').next())
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0);
+
+    // `--quiet` only prints a dot per test, the names of failed tests are listed at the end.
+    let failed = stdout
+        .rsplit_once("\nfailures:\n")
+        .map(|(_, list)| {
+            list.lines()
+                .map_while(|l| l.strip_prefix("    "))
+                .map(|name| name.rsplit("::").next().unwrap_or(name).to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    TestOutcome::Finished { passed, failed }
+}
+
+/// How an answer compares to the one of the previous run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnswerChange {
+    First,
+    Unchanged,
+    Changed { previous: Option<String> },
+}
+
+impl AnswerChange {
+    pub fn new(previous: Option<&Option<String>>, current: &Option<String>) -> Self {
+        match previous {
+            None => AnswerChange::First,
+            Some(previous) if previous == current => AnswerChange::Unchanged,
+            Some(previous) => AnswerChange::Changed {
+                previous: previous.clone(),
+            },
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime};
+
+    use super::{changed_files, parse_test_output, AnswerChange, Snapshot, TestOutcome};
+
+    #[test]
+    fn detects_changed_files() {
+        let t = SystemTime::UNIX_EPOCH;
+        let previous: Snapshot = [
+            (PathBuf::from("a"), t),
+            (PathBuf::from("b"), t),
+            (PathBuf::from("c"), t),
+        ]
+        .into();
+        let current: Snapshot = [
+            (PathBuf::from("a"), t),
+            (PathBuf::from("b"), t + Duration::from_secs(1)),
+            (PathBuf::from("d"), t),
+        ]
+        .into();
+
+        assert_eq!(
+            changed_files(&previous, &current),
+            vec![PathBuf::from("b"), PathBuf::from("d"), PathBuf::from("c")]
+        );
+        assert_eq!(changed_files(&current, &current).len(), 0);
+    }
+
+    #[test]
+    fn parses_test_output() {
+        let stdout = "\nrunning 2 tests\n.F\nfailures:\n\n---- tests::test_part_two stdout ----\nassertion `left == right` failed\n  left: Some(1)\n right: Some(2)\n\nfailures:\n    tests::test_part_two\n\ntest result: FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out\n";
+        assert_eq!(
+            parse_test_output(stdout, ""),
+            TestOutcome::Finished {
+                passed: 1,
+                failed: vec!["test_part_two".into()]
+            }
+        );
+
+        let stderr = "   Compiling advent_of_code v0.11.0\nerror[E0425]: cannot find value `x` in this scope\n --> src/bin/01.rs:4:5\n";
+        assert_eq!(
+            parse_test_output("", stderr),
+            TestOutcome::CompileError(
+                "error[E0425]: cannot find value `x` in this scope\n --> src/bin/01.rs:4:5".into()
+            )
+        );
+    }
+
+    #[test]
+    fn compares_answers() {
+        let answer = Some("42".to_string());
+        assert_eq!(AnswerChange::new(None, &answer), AnswerChange::First);
+        assert_eq!(
+            AnswerChange::new(Some(&answer), &answer),
+            AnswerChange::Unchanged
+        );
+        assert_eq!(
+            AnswerChange::new(Some(&None), &answer),
+            AnswerChange::Changed { previous: None }
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! Evaluating and ordering hands of cards: hands are ranked by their category first and then
+//! card by card, with the card order, the wildcards and the categories given by [`Rules`].
+
+/// Categories from weakest to strongest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Category {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+/// Categorizes a hand by the sizes of its groups of equal cards, largest first.
+pub type CategoryRule = fn(&[usize]) -> Category;
+
+/// The categories of Camel Cards, poker without straights and flushes.
+pub fn camel_cards(groups: &[usize]) -> Category {
+    match groups {
+        [5, ..] => Category::FiveOfAKind,
+        [4, ..] => Category::FourOfAKind,
+        [3, 2, ..] => Category::FullHouse,
+        [3, ..] => Category::ThreeOfAKind,
+        [2, 2, ..] => Category::TwoPair,
+        [2, ..] => Category::OnePair,
+        _ => Category::HighCard,
+    }
+}
+
+/// An evaluated hand. The derived order compares the category first and then the card ranks in order.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Hand {
+    pub category: Category,
+    pub ranks: Vec<u8>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Rules {
+    /// Rank of every card, `None` for cards that are not in the game.
+    ranks: [Option<u8>; 256],
+    wildcards: [bool; 256],
+    categories: CategoryRule,
+}
+
+impl Rules {
+    /// `order` lists the cards from weakest to strongest. Wildcards take the place of whatever
+    /// card makes the best category, but keep their own rank when hands are compared card by card.
+    pub fn new(order: &str, wildcards: &str, categories: CategoryRule) -> Self {
+        let mut ranks = [None; 256];
+        for (rank, card) in order.bytes().enumerate() {
+            ranks[card as usize] = Some(rank as u8);
+        }
+        let mut is_wildcard = [false; 256];
+        for card in wildcards.bytes() {
+            is_wildcard[card as usize] = true;
+        }
+        Self {
+            ranks,
+            wildcards: is_wildcard,
+            categories,
+        }
+    }
+
+    pub fn evaluate(&self, cards: &str) -> Result<Hand, String> {
+        let ranks = cards
+            .bytes()
+            .map(|c| self.ranks[c as usize].ok_or(format!("{} is an invalid card", c as char)))
+            .collect::<Result<Vec<u8>, String>>()?;
+
+        let mut counts = [0; 256];
+        let mut wildcards = 0;
+        for card in cards.bytes() {
+            if self.wildcards[card as usize] {
+                wildcards += 1;
+            } else {
+                counts[card as usize] += 1;
+            }
+        }
+
+        let mut groups: Vec<usize> = counts.into_iter().filter(|c| *c > 0).collect();
+        groups.sort_unstable_by(|a, b| b.cmp(a));
+        // wildcards always do best joining the largest group.
+        match groups.first_mut() {
+            Some(largest) => *largest += wildcards,
+            None if wildcards > 0 => groups.push(wildcards),
+            None => {}
+        }
+
+        Ok(Hand {
+            category: (self.categories)(&groups),
+            ranks,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn category(rules: &Rules, cards: &str) -> Category {
+        rules.evaluate(cards).unwrap().category
+    }
+
+    #[test]
+    fn test_categories() {
+        let rules = Rules::new("23456789TJQKA", "", camel_cards);
+        assert_eq!(category(&rules, "AAAAA"), Category::FiveOfAKind);
+        assert_eq!(category(&rules, "AA8AA"), Category::FourOfAKind);
+        assert_eq!(category(&rules, "23332"), Category::FullHouse);
+        assert_eq!(category(&rules, "TTT98"), Category::ThreeOfAKind);
+        assert_eq!(category(&rules, "23432"), Category::TwoPair);
+        assert_eq!(category(&rules, "A23A4"), Category::OnePair);
+        assert_eq!(category(&rules, "23456"), Category::HighCard);
+    }
+
+    #[test]
+    fn test_wildcards() {
+        let rules = Rules::new("J23456789TQKA", "J", camel_cards);
+        assert_eq!(category(&rules, "JJJJJ"), Category::FiveOfAKind);
+        assert_eq!(category(&rules, "KTJJT"), Category::FourOfAKind);
+        assert_eq!(category(&rules, "2233J"), Category::FullHouse);
+        assert_eq!(category(&rules, "2345J"), Category::OnePair);
+
+        // wildcards rank by their own place in the order.
+        assert!(rules.evaluate("JKKK2").unwrap() < rules.evaluate("QQQQ2").unwrap());
+        assert!(rules.evaluate("JKKK2").unwrap() < rules.evaluate("2KKKK").unwrap());
+
+        let two_wildcards = Rules::new("JQ23456789TKA", "JQ", camel_cards);
+        assert_eq!(category(&two_wildcards, "2QJ34"), Category::ThreeOfAKind);
+    }
+
+    #[test]
+    fn test_invalid_card() {
+        let rules = Rules::new("23456789TJQKA", "", camel_cards);
+        assert_eq!(
+            rules.evaluate("2345X"),
+            Err("X is an invalid card".to_string())
+        );
+    }
+}
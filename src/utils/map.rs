@@ -37,6 +37,17 @@ impl Map {
         distance as usize
     }
 
+    /// Sum of the Manhattan distances between all pairs of positions.
+    /// The axes are independent, so each is sorted and summed with [`sum_pairwise_distances_sorted`] in O(n log n).
+    pub fn sum_pairwise_distances(positions: &[Position]) -> u64 {
+        let axis = |coordinate: fn(&Position) -> usize| {
+            let mut values: Vec<u64> = positions.iter().map(|p| coordinate(p) as u64).collect();
+            values.sort_unstable();
+            sum_pairwise_distances_sorted(values.into_iter().map(|v| (v, 1)))
+        };
+        axis(|p| p.0) + axis(|p| p.1)
+    }
+
     pub fn move_vertical(current_pos: &Position, previous_pos: &Position) -> Position {
         let y_coord = current_pos.1;
         if previous_pos.1 < y_coord {
@@ -91,6 +102,50 @@ impl Map {
     }
 }
 
+/// Sum of the distances between all pairs of points on a line, in linear time.
+/// Points are given in ascending order as `(coordinate, count)`, so that a row or column holding several points is summed at once.
+pub fn sum_pairwise_distances_sorted(points: impl IntoIterator<Item = (u64, u64)>) -> u64 {
+    let mut count_before = 0;
+    let mut sum_before = 0;
+    let mut total = 0;
+
+    for (coordinate, count) in points {
+        // every point is `coordinate - c` away from each point `c` before it.
+        total += count * (count_before * coordinate - sum_before);
+        count_before += count;
+        sum_before += count * coordinate;
+    }
+
+    total
+}
+
+/// Expands a grid by growing every empty row (or column) to `factor` rows, based on prefix counts of the empty ones.
+pub struct Expansion {
+    empty_before: Vec<u64>,
+}
+
+impl Expansion {
+    /// `occupied[i]` tells whether row `i` contains anything.
+    pub fn new(occupied: &[bool]) -> Self {
+        let empty_before = occupied
+            .iter()
+            .scan(0, |empty, is_occupied| {
+                let before = *empty;
+                if !is_occupied {
+                    *empty += 1;
+                }
+                Some(before)
+            })
+            .collect();
+        Expansion { empty_before }
+    }
+
+    /// Coordinate of row `index` after the expansion.
+    pub fn expand(&self, index: usize, factor: u64) -> u64 {
+        index as u64 + self.empty_before[index] * (factor - 1)
+    }
+}
+
 impl Direction {
     pub fn opposite(&self) -> Self {
         match self {
@@ -130,6 +185,32 @@ impl Direction {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::utils::testing::Rng;
+
+    #[test]
+    fn test_sum_pairwise_distances() {
+        let mut rng = Rng::new(11);
+        let positions: Vec<Position> = (0..50).map(|_| (rng.index(20), rng.index(20))).collect();
+
+        let mut expected = 0;
+        for (i, a) in positions.iter().enumerate() {
+            for b in &positions[i + 1..] {
+                expected += Map::get_distance(*a, *b) as u64;
+            }
+        }
+
+        assert_eq!(Map::sum_pairwise_distances(&positions), expected);
+        assert_eq!(Map::sum_pairwise_distances(&[]), 0);
+    }
+
+    #[test]
+    fn test_expansion() {
+        let expansion = Expansion::new(&[true, false, true, false, false, true]);
+        assert_eq!(expansion.expand(0, 10), 0);
+        assert_eq!(expansion.expand(2, 10), 11);
+        assert_eq!(expansion.expand(5, 10), 32);
+        assert_eq!(expansion.expand(5, 1), 5);
+    }
 
     #[test]
     fn test_get_distance() {
@@ -0,0 +1,88 @@
+use std::ops::RangeInclusive;
+
+/// Integer square root: the largest `r` with `r * r <= n`.
+/// Starts from the floating-point estimate, which is inexact for large `n`, and corrects it until `r * r <= n < (r + 1)²`.
+pub fn isqrt(n: u128) -> u128 {
+    let mut root = (n as f64).sqrt() as u128;
+    let square = |r: u128| r.checked_mul(r);
+    while square(root).is_none_or(|sq| sq > n) {
+        root -= 1;
+    }
+    while square(root + 1).is_some_and(|sq| sq <= n) {
+        root += 1;
+    }
+    root
+}
+
+/// Integers `x` in `0..=sum` for which `x * (sum - x) > threshold`, e.g. the hold times that beat a boat race record.
+///
+/// The product is an inverted parabola with roots `(sum ± sqrt(sum² - 4 * threshold)) / 2`.
+/// The root is computed with [`isqrt`] and moved to the exact integer boundary, so no floating-point error creeps in.
+pub fn above_inverted_parabola(sum: u64, threshold: u64) -> Option<RangeInclusive<u64>> {
+    let product = |x: u64| u128::from(x) * u128::from(sum - x);
+    let threshold_wide = u128::from(threshold);
+
+    // the maximum is at the middle.
+    if product(sum / 2) <= threshold_wide {
+        return None;
+    }
+
+    let sum_wide = u128::from(sum);
+    let discriminant = sum_wide * sum_wide - 4 * threshold_wide;
+    let mut lower = ((sum_wide - isqrt(discriminant)) / 2) as u64;
+
+    while lower > 0 && product(lower - 1) > threshold_wide {
+        lower -= 1;
+    }
+    while product(lower) <= threshold_wide {
+        lower += 1;
+    }
+
+    // the parabola is symmetric around `sum / 2`.
+    Some(lower..=sum - lower)
+}
+
+/// Number of integers in [`above_inverted_parabola`].
+pub fn count_above_inverted_parabola(sum: u64, threshold: u64) -> u64 {
+    above_inverted_parabola(sum, threshold).map_or(0, |range| range.end() - range.start() + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::testing::Rng;
+
+    #[test]
+    fn test_isqrt() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        let large = u128::from(u64::MAX);
+        assert_eq!(isqrt(large * large), large);
+        assert_eq!(isqrt(large * large - 1), large - 1);
+    }
+
+    #[test]
+    fn test_above_inverted_parabola() {
+        assert_eq!(above_inverted_parabola(7, 9), Some(2..=5));
+        assert_eq!(above_inverted_parabola(30, 200), Some(11..=19));
+        // exactly matching the threshold is not enough.
+        assert_eq!(above_inverted_parabola(4, 4), None);
+        assert_eq!(count_above_inverted_parabola(71530, 940200), 71503);
+    }
+
+    #[test]
+    fn test_above_inverted_parabola_against_brute_force() {
+        let mut rng = Rng::new(6);
+        for _ in 0..1000 {
+            let sum = rng.range(0, 200) as u64;
+            let threshold = rng.range(0, 10_000) as u64;
+            let expected = (0..=sum).filter(|x| x * (sum - x) > threshold).count() as u64;
+            assert_eq!(
+                count_above_inverted_parabola(sum, threshold),
+                expected,
+                "sum {sum}, threshold {threshold}"
+            );
+        }
+    }
+}
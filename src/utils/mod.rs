@@ -1,2 +1,7 @@
+pub mod cards;
 pub mod map;
+pub mod math;
+pub mod nonogram;
 pub mod parsers;
+pub mod pipes;
+pub mod testing;
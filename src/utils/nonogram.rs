@@ -0,0 +1,162 @@
+/// Counting the arrangements of a nonogram line: cells are filled, empty or unknown,
+/// and the runs of filled cells have to match a list of run lengths in order.
+use std::mem;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cell {
+    Empty,
+    Filled,
+    Unknown,
+}
+
+impl TryFrom<u8> for Cell {
+    type Error = String;
+
+    /// Reads the usual puzzle notation: `.` empty, `#` filled and `?` unknown.
+    fn try_from(c: u8) -> Result<Self, Self::Error> {
+        match c {
+            b'.' => Ok(Cell::Empty),
+            b'#' => Ok(Cell::Filled),
+            b'?' => Ok(Cell::Unknown),
+            _ => Err(format!("Unknown cell {:?}", char::from(c))),
+        }
+    }
+}
+
+/// Counts arrangements with a bottom-up table over (position, run).
+/// The buffers are kept between calls, so counting many lines with one counter does not allocate.
+#[derive(Debug, Default)]
+pub struct LineCounter {
+    /// Number of consecutive cells that may be filled, starting at each position.
+    run_lengths: Vec<usize>,
+    /// Arrangements of the remaining runs in the remaining cells, for the current and the following run.
+    current: Vec<u64>,
+    next: Vec<u64>,
+}
+
+impl LineCounter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of ways to fill the unknown cells so that the runs of filled cells match `runs`.
+    /// Runs of length zero can't be placed, lines requiring them have no arrangement.
+    pub fn count(&mut self, cells: &[Cell], runs: &[usize]) -> u64 {
+        let n = cells.len();
+
+        self.run_lengths.clear();
+        self.run_lengths.resize(n + 1, 0);
+        for i in (0..n).rev() {
+            if cells[i] != Cell::Empty {
+                self.run_lengths[i] = self.run_lengths[i + 1] + 1;
+            }
+        }
+
+        // without runs left, the rest of the line has to be empty.
+        self.next.clear();
+        self.next.resize(n + 1, 0);
+        self.next[n] = 1;
+        for i in (0..n).rev() {
+            if cells[i] != Cell::Filled {
+                self.next[i] = self.next[i + 1];
+            }
+        }
+
+        for &run in runs.iter().rev() {
+            self.current.clear();
+            self.current.resize(n + 1, 0);
+
+            for i in (0..n).rev() {
+                // leave the cell empty.
+                let mut ways = if cells[i] == Cell::Filled {
+                    0
+                } else {
+                    self.current[i + 1]
+                };
+
+                // start the run here, it has to be followed by an empty cell or the end of the line.
+                let end = i + run;
+                if run > 0 && self.run_lengths[i] >= run && (end == n || cells[end] != Cell::Filled)
+                {
+                    ways += self.next[(end + 1).min(n)];
+                }
+
+                self.current[i] = ways;
+            }
+
+            mem::swap(&mut self.current, &mut self.next);
+        }
+
+        self.next[0]
+    }
+}
+
+/// Counts the arrangements of a single line, see [`LineCounter::count`].
+pub fn count_arrangements(cells: &[Cell], runs: &[usize]) -> u64 {
+    LineCounter::new().count(cells, runs)
+}
+
+/// Brute-force reference for [`LineCounter::count`]: tries every fill of the unknown cells.
+/// Exponential in the number of unknown cells, meant for checking the counter on short lines in tests.
+pub fn count_exhaustive(cells: &[Cell], runs: &[usize]) -> u64 {
+    let unknown: Vec<usize> = (0..cells.len())
+        .filter(|i| cells[*i] == Cell::Unknown)
+        .collect();
+
+    (0..1_u64 << unknown.len())
+        .filter(|mask| {
+            let mut filled: Vec<bool> = cells.iter().map(|c| *c == Cell::Filled).collect();
+            for (bit, i) in unknown.iter().enumerate() {
+                filled[*i] = mask & (1 << bit) != 0;
+            }
+            let actual: Vec<usize> = filled
+                .split(|f| !f)
+                .map(<[bool]>::len)
+                .filter(|len| *len > 0)
+                .collect();
+            actual == runs
+        })
+        .count() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::testing::Rng;
+
+    fn parse(line: &str) -> Vec<Cell> {
+        line.bytes().map(|c| Cell::try_from(c).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_count_arrangements() {
+        assert_eq!(count_arrangements(&parse("???.###"), &[1, 1, 3]), 1);
+        assert_eq!(count_arrangements(&parse("?###????????"), &[3, 2, 1]), 10);
+        assert_eq!(count_arrangements(&parse("#"), &[2]), 0);
+    }
+
+    #[test]
+    fn test_count_arrangements_without_runs() {
+        assert_eq!(count_arrangements(&parse("?.?"), &[]), 1);
+        assert_eq!(count_arrangements(&parse("?#?"), &[]), 0);
+        assert_eq!(count_arrangements(&[], &[]), 1);
+        assert_eq!(count_arrangements(&[], &[1]), 0);
+    }
+
+    #[test]
+    fn test_count_arrangements_against_exhaustive() {
+        let mut rng = Rng::new(12);
+        let mut counter = LineCounter::new();
+        for _ in 0..1000 {
+            let cells: Vec<Cell> = (0..rng.index(12))
+                .map(|_| *rng.choose(&[Cell::Empty, Cell::Filled, Cell::Unknown]))
+                .collect();
+            let runs: Vec<usize> = (0..rng.index(4)).map(|_| rng.index(4) + 1).collect();
+            assert_eq!(
+                counter.count(&cells, &runs),
+                count_exhaustive(&cells, &runs),
+                "{cells:?} {runs:?}"
+            );
+        }
+    }
+}
@@ -9,16 +9,180 @@ where
     <T as FromStr>::Err: Debug,
 {
     let maps: Vec<&str> = line.split(' ').collect();
-    maps.into_iter()
-        .map(|x| x.parse::<T>())
-        .filter(|x| x.is_ok())
-        .map(|x| x.unwrap())
+    maps.into_iter().filter_map(|x| x.parse::<T>().ok())
 }
 
 pub fn par_parse_line<'a>(line: &'a str) -> impl rayon::iter::ParallelIterator<Item = i64> + 'a {
     let maps: Vec<&str> = line.split(' ').collect();
-    maps.into_par_iter()
-        .map(|x| x.parse::<i64>())
-        .filter(|x| x.is_ok())
-        .map(|x| x.unwrap())
+    maps.into_par_iter().filter_map(|x| x.parse::<i64>().ok())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An occurrence of one of the patterns of a [`PatternMatcher`], `start..end` being its byte range.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Match<'a, T> {
+    pub start: usize,
+    pub end: usize,
+    pub value: &'a T,
+}
+
+// derived impls would require `T: Copy`, the match only holds a reference.
+impl<T> Clone for Match<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Match<'_, T> {}
+
+struct State {
+    /// Next state for every byte, failure transitions already folded in.
+    next: [u32; 256],
+    /// Patterns ending in this state, longest first.
+    outputs: Vec<usize>,
+}
+
+/// Finds all occurrences of a vocabulary of patterns in a single pass over the text, overlapping
+/// ones included, e.g. both "eight" and "two" in "eightwo".
+///
+/// An Aho–Corasick automaton: a trie of the patterns where every missing transition continues in
+/// the longest suffix that is also a prefix of a pattern.
+pub struct PatternMatcher<T> {
+    states: Vec<State>,
+    patterns: Vec<(usize, T)>,
+}
+
+impl<T> PatternMatcher<T> {
+    /// Builds the matcher from patterns and the values they stand for. Empty patterns never match.
+    pub fn new<'p>(vocabulary: impl IntoIterator<Item = (&'p str, T)>) -> Self {
+        const MISSING: u32 = u32::MAX;
+        let new_state = || State {
+            next: [MISSING; 256],
+            outputs: vec![],
+        };
+
+        let mut states = vec![new_state()];
+        let mut patterns = vec![];
+        for (pattern, value) in vocabulary {
+            if pattern.is_empty() {
+                continue;
+            }
+            let mut state = 0;
+            for b in pattern.bytes() {
+                if states[state].next[b as usize] == MISSING {
+                    states[state].next[b as usize] = states.len() as u32;
+                    states.push(new_state());
+                }
+                state = states[state].next[b as usize] as usize;
+            }
+            states[state].outputs.push(patterns.len());
+            patterns.push((pattern.len(), value));
+        }
+
+        // breadth-first, so the failure state of every state is complete before its children.
+        let mut fail = vec![0; states.len()];
+        let mut queue = std::collections::VecDeque::new();
+        for b in 0..256 {
+            match states[0].next[b] {
+                MISSING => states[0].next[b] = 0,
+                child => queue.push_back(child as usize),
+            }
+        }
+        while let Some(state) = queue.pop_front() {
+            let outputs = states[fail[state]].outputs.clone();
+            states[state].outputs.extend(outputs);
+            for b in 0..256 {
+                let fallback = states[fail[state]].next[b];
+                match states[state].next[b] {
+                    MISSING => states[state].next[b] = fallback,
+                    child => {
+                        fail[child as usize] = fallback as usize;
+                        queue.push_back(child as usize);
+                    }
+                }
+            }
+        }
+
+        Self { states, patterns }
+    }
+
+    /// All matches, ordered by their end.
+    pub fn find_iter<'a>(&'a self, text: &'a str) -> impl Iterator<Item = Match<'a, T>> + 'a {
+        text.bytes()
+            .enumerate()
+            .scan(0, move |state, (i, b)| {
+                *state = self.states[*state].next[b as usize] as usize;
+                Some((i + 1, *state))
+            })
+            .flat_map(move |(end, state)| {
+                self.states[state].outputs.iter().map(move |p| {
+                    let (len, value) = &self.patterns[*p];
+                    Match {
+                        start: end - len,
+                        end,
+                        value,
+                    }
+                })
+            })
+    }
+
+    /// The matches starting first and last, found in one scan.
+    pub fn find_first_and_last<'a>(
+        &'a self,
+        text: &'a str,
+    ) -> Option<(Match<'a, T>, Match<'a, T>)> {
+        self.find_iter(text).fold(None, |found, m| match found {
+            None => Some((m, m)),
+            Some((first, last)) => Some((
+                if m.start < first.start { m } else { first },
+                if m.start > last.start { m } else { last },
+            )),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn positions<'a, T: 'a>(matches: impl Iterator<Item = Match<'a, T>>) -> Vec<(usize, usize)> {
+        matches.map(|m| (m.start, m.end)).collect()
+    }
+
+    #[test]
+    fn test_parse_line() {
+        let numbers: Vec<i32> = parse_line("seeds: 79 14 -55").collect();
+        assert_eq!(numbers, vec![79, 14, -55]);
+    }
+
+    #[test]
+    fn test_overlapping_matches() {
+        let matcher = PatternMatcher::new([("eight", 8), ("two", 2), ("one", 1)]);
+        let values: Vec<i32> = matcher.find_iter("eightwone").map(|m| *m.value).collect();
+        assert_eq!(values, vec![8, 2, 1]);
+        assert_eq!(
+            positions(matcher.find_iter("eightwone")),
+            vec![(0, 5), (4, 7), (6, 9)]
+        );
+    }
+
+    #[test]
+    fn test_nested_patterns() {
+        let matcher = PatternMatcher::new([("he", 0), ("she", 1), ("hers", 2), ("e", 3)]);
+        assert_eq!(
+            positions(matcher.find_iter("ushers")),
+            vec![(1, 4), (2, 4), (3, 4), (2, 6)]
+        );
+        assert_eq!(matcher.find_iter("").count(), 0);
+    }
+
+    #[test]
+    fn test_find_first_and_last() {
+        let matcher = PatternMatcher::new([("abc", 'a'), ("b", 'b'), ("", 'x')]);
+        let (first, last) = matcher.find_first_and_last("xbabcx").unwrap();
+        assert_eq!((*first.value, first.start), ('b', 1));
+        assert_eq!((*last.value, last.start), ('b', 3));
+        assert!(matcher.find_first_and_last("xyz").is_none());
+    }
 }
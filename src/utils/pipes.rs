@@ -0,0 +1,285 @@
+/// A grid of pipes as in the pipe maze: `|`, `-`, `L`, `J`, `7` and `F` connect two of their
+/// neighbours, `S` marks the start of the loop and anything else is ground.
+use std::fmt;
+
+pub const NORTH: u8 = 1;
+pub const SOUTH: u8 = 2;
+pub const WEST: u8 = 4;
+pub const EAST: u8 = 8;
+
+/// The sides a tile connects to.
+pub fn connections(tile: u8) -> u8 {
+    match tile {
+        b'|' => NORTH | SOUTH,
+        b'-' => WEST | EAST,
+        b'L' => NORTH | EAST,
+        b'J' => NORTH | WEST,
+        b'7' => SOUTH | WEST,
+        b'F' => SOUTH | EAST,
+        _ => 0,
+    }
+}
+
+/// The tile connecting exactly the given sides.
+pub fn tile_from_connections(sides: u8) -> Option<u8> {
+    b"|-LJ7F"
+        .iter()
+        .copied()
+        .find(|tile| connections(*tile) == sides)
+}
+
+fn opposite(side: u8) -> u8 {
+    match side {
+        NORTH => SOUTH,
+        SOUTH => NORTH,
+        WEST => EAST,
+        _ => WEST,
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PipeError {
+    NoStart,
+    /// Holds the 0-based index of the first row that differs in length from the first one.
+    UnevenRows(usize),
+    /// No pair of the neighbours connecting to the start closes the loop, holds the number of those neighbours.
+    AmbiguousStart(usize),
+    BrokenLoop(usize),
+}
+
+impl fmt::Display for PipeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PipeError::NoStart => write!(f, "No starting position found"),
+            PipeError::UnevenRows(row) => {
+                write!(f, "Row {row} differs in length from the first row")
+            }
+            PipeError::AmbiguousStart(n) => {
+                write!(
+                    f,
+                    "No loop through the start and its {n} connected neighbours"
+                )
+            }
+            PipeError::BrokenLoop(cell) => write!(f, "The loop is broken at cell {cell}"),
+        }
+    }
+}
+
+/// Cells that are part of the loop, one bit each.
+#[derive(Clone, Debug)]
+pub struct Bitset(Vec<u64>);
+
+impl Bitset {
+    pub fn new(len: usize) -> Self {
+        Bitset(vec![0; len.div_ceil(64)])
+    }
+
+    pub fn insert(&mut self, i: usize) {
+        self.0[i / 64] |= 1 << (i % 64);
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        self.0[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().all(|w| *w == 0)
+    }
+}
+
+/// A flat, row-major grid of pipes with the start replaced by the tile it stands for.
+#[derive(Clone, Debug)]
+pub struct PipeGrid {
+    pub width: usize,
+    pub height: usize,
+    pub tiles: Vec<u8>,
+    pub start: usize,
+}
+
+impl PipeGrid {
+    pub fn parse(input: &str) -> Result<Self, PipeError> {
+        let lines: Vec<&[u8]> = input.lines().map(str::as_bytes).collect();
+        let width = lines.first().map_or(0, |l| l.len());
+        if let Some(row) = lines.iter().position(|l| l.len() != width) {
+            return Err(PipeError::UnevenRows(row));
+        }
+        let mut grid = PipeGrid {
+            width,
+            height: lines.len(),
+            tiles: lines.concat(),
+            start: 0,
+        };
+
+        grid.start = grid
+            .tiles
+            .iter()
+            .position(|t| *t == b'S')
+            .ok_or(PipeError::NoStart)?;
+        grid.tiles[grid.start] = grid.infer_start()?;
+        Ok(grid)
+    }
+
+    /// The start connects to two of the neighbours that connect back to it.
+    /// With more than two, stray pipes point at the start, and the pair that closes the loop is used.
+    fn infer_start(&mut self) -> Result<u8, PipeError> {
+        let sides: Vec<u8> = [NORTH, SOUTH, WEST, EAST]
+            .into_iter()
+            .filter(|side| {
+                self.neighbour(self.start, *side)
+                    .is_some_and(|n| connections(self.tiles[n]) & opposite(*side) != 0)
+            })
+            .collect();
+
+        if let [a, b] = sides[..] {
+            return Ok(tile_from_connections(a | b).unwrap());
+        }
+        for (i, a) in sides.iter().enumerate() {
+            for b in &sides[i + 1..] {
+                let tile = tile_from_connections(a | b).unwrap();
+                self.tiles[self.start] = tile;
+                if self.find_loop().is_ok() {
+                    return Ok(tile);
+                }
+            }
+        }
+        Err(PipeError::AmbiguousStart(sides.len()))
+    }
+
+    pub fn neighbour(&self, cell: usize, side: u8) -> Option<usize> {
+        let (x, y) = (cell % self.width, cell / self.width);
+        match side {
+            NORTH => (y > 0).then(|| cell - self.width),
+            SOUTH => (y + 1 < self.height).then(|| cell + self.width),
+            WEST => (x > 0).then(|| cell - 1),
+            _ => (x + 1 < self.width).then(|| cell + 1),
+        }
+    }
+
+    /// Follows the pipes from the start until it is reached again.
+    pub fn find_loop(&self) -> Result<Bitset, PipeError> {
+        let mut cells = Bitset::new(self.tiles.len());
+        let mut cell = self.start;
+        // leave the start through its lowest side.
+        let mut side = 1 << connections(self.tiles[cell]).trailing_zeros();
+        loop {
+            cells.insert(cell);
+            let next = self
+                .neighbour(cell, side)
+                .ok_or(PipeError::BrokenLoop(cell))?;
+            let sides = connections(self.tiles[next]);
+            if sides & opposite(side) == 0 {
+                return Err(PipeError::BrokenLoop(next));
+            }
+            if next == self.start {
+                return Ok(cells);
+            }
+            side = sides & !opposite(side);
+            cell = next;
+        }
+    }
+
+    /// Number of tiles enclosed by the loop.
+    /// Scanning each row, the inside flips on every loop tile that connects north.
+    pub fn count_enclosed(&self, cells: &Bitset) -> usize {
+        let mut count = 0;
+        for (y, tiles) in self.tiles.chunks(self.width).enumerate() {
+            let mut inside = false;
+            for (x, tile) in tiles.iter().enumerate() {
+                let cell = y * self.width + x;
+                if cells.contains(cell) {
+                    inside ^= connections(*tile) & NORTH != 0;
+                } else if inside {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Draws the loop with box-drawing characters, enclosed tiles as `•` and everything else blank.
+    pub fn render(&self, cells: &Bitset) -> String {
+        let mut output = String::with_capacity(self.tiles.len() * 3 + self.height);
+        for (y, tiles) in self.tiles.chunks(self.width).enumerate() {
+            let mut inside = false;
+            for (x, tile) in tiles.iter().enumerate() {
+                let c = if cells.contains(y * self.width + x) {
+                    inside ^= connections(*tile) & NORTH != 0;
+                    match tile {
+                        b'|' => '│',
+                        b'-' => '─',
+                        b'L' => '└',
+                        b'J' => '┘',
+                        b'7' => '┐',
+                        _ => '┌',
+                    }
+                } else if inside {
+                    '•'
+                } else {
+                    ' '
+                };
+                output.push(c);
+            }
+            output.push('\n');
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE: &str = ".....\n.S-7.\n.|.|.\n.L-J.\n.....";
+
+    fn start_tile(input: &str) -> Result<u8, PipeError> {
+        PipeGrid::parse(input).map(|grid| grid.tiles[grid.start])
+    }
+
+    #[test]
+    fn test_infer_start() {
+        assert_eq!(start_tile(".|.\n.S.\n.|."), Ok(b'|'));
+        assert_eq!(start_tile("...\n-S-\n..."), Ok(b'-'));
+        assert_eq!(start_tile(".|.\n.S-\n..."), Ok(b'L'));
+        assert_eq!(start_tile(".|.\n-S.\n..."), Ok(b'J'));
+        assert_eq!(start_tile("...\n-S.\n.|."), Ok(b'7'));
+        assert_eq!(start_tile("S-\n|."), Ok(b'F'));
+        // the pipe above points at the start, but isn't part of the loop.
+        assert_eq!(start_tile(".|...\n.S-7.\n.|.|.\n.L-J.\n....."), Ok(b'F'));
+        assert_eq!(
+            start_tile("...\n.S-\n..."),
+            Err(PipeError::AmbiguousStart(1))
+        );
+        assert_eq!(start_tile("..."), Err(PipeError::NoStart));
+        assert_eq!(start_tile("\nS-7\n|.|\nL-J"), Err(PipeError::UnevenRows(1)));
+    }
+
+    #[test]
+    fn test_find_loop() {
+        let grid = PipeGrid::parse(SQUARE).unwrap();
+        let cells = grid.find_loop().unwrap();
+        assert_eq!(cells.len(), 8);
+        assert!(cells.contains(6) && !cells.contains(12));
+
+        let broken = PipeGrid::parse(".....\n.S-7.\n.|.|.\n.L-..\n.....").unwrap();
+        assert!(broken.find_loop().is_err());
+    }
+
+    #[test]
+    fn test_count_enclosed() {
+        let grid = PipeGrid::parse(SQUARE).unwrap();
+        assert_eq!(grid.count_enclosed(&grid.find_loop().unwrap()), 1);
+    }
+
+    #[test]
+    fn test_render() {
+        let grid = PipeGrid::parse(SQUARE).unwrap();
+        assert_eq!(
+            grid.render(&grid.find_loop().unwrap()),
+            "     \n ┌─┐ \n │•│ \n └─┘ \n     \n"
+        );
+    }
+}
@@ -0,0 +1,248 @@
+/// Property-based and differential testing of solutions.
+///
+/// A [`Generator`] produces random puzzle inputs and knows how to make them smaller.
+/// [`check`] runs a property against many generated inputs, [`differential`] compares two implementations,
+/// e.g. a brute-force reference against an optimized solver. A failing input is shrunk to a minimal
+/// counterexample before the test panics.
+///
+/// The number of cases and the seed can be set with `AOC_TEST_CASES` and `AOC_TEST_SEED`.
+use std::env;
+use std::fmt::Debug;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Small xorshift random number generator, good enough to generate test inputs.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // the state must never be zero, xorshift would only produce zeros.
+        Rng {
+            state: (seed ^ 0x9E37_79B9_7F4A_7C15) | 1,
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /// Random number in `min..=max`.
+    pub fn range(&mut self, min: i64, max: i64) -> i64 {
+        assert!(min <= max, "empty range {min}..={max}");
+        let span = max.abs_diff(min).wrapping_add(1);
+        if span == 0 {
+            // the range covers all of i64.
+            return self.next_u64() as i64;
+        }
+        min.wrapping_add((self.next_u64() % span) as i64)
+    }
+
+    /// Random index in `0..len`.
+    pub fn index(&mut self, len: usize) -> usize {
+        assert!(len > 0, "cannot pick from an empty range");
+        (self.next_u64() % len as u64) as usize
+    }
+
+    pub fn bool(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+}
+
+/// Generates random values of a puzzle input.
+pub trait Generator {
+    type Value: Clone + Debug;
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value;
+
+    /// Smaller variants of a value, tried in order when shrinking a counterexample.
+    /// Every candidate should be strictly smaller than `value`, otherwise shrinking may not terminate.
+    fn shrink(&self, _value: &Self::Value) -> Vec<Self::Value> {
+        vec![]
+    }
+}
+
+/// Shrinks a list by removing single elements, starting with the first and last half.
+pub fn shrink_vec<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    let mut candidates = vec![];
+
+    if items.len() > 1 {
+        let half = items.len() / 2;
+        candidates.push(items[..half].to_vec());
+        candidates.push(items[half..].to_vec());
+    }
+
+    for i in 0..items.len() {
+        let mut smaller = items.to_vec();
+        smaller.remove(i);
+        candidates.push(smaller);
+    }
+
+    candidates
+}
+
+/// Shrinks a number towards `target`.
+pub fn shrink_int(value: i64, target: i64) -> Vec<i64> {
+    if value == target {
+        return vec![];
+    }
+
+    let mut candidates = vec![target];
+    let halfway = target + (value - target) / 2;
+    if halfway != target && halfway != value {
+        candidates.push(halfway);
+    }
+    let step = if value > target { value - 1 } else { value + 1 };
+    if step != target && step != halfway {
+        candidates.push(step);
+    }
+    candidates
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn env_or<T: std::str::FromStr>(key: &str, default: T) -> T {
+    env::var(key)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+fn get_seed() -> u64 {
+    env_or(
+        "AOC_TEST_SEED",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64),
+    )
+}
+
+/// Greedily shrinks a failing value until none of its shrink candidates fails anymore.
+fn minimize<G: Generator>(
+    generator: &G,
+    mut value: G::Value,
+    mut error: String,
+    property: &impl Fn(&G::Value) -> Result<(), String>,
+) -> (G::Value, String, usize) {
+    let mut steps = 0;
+
+    'outer: loop {
+        for candidate in generator.shrink(&value) {
+            if let Err(e) = property(&candidate) {
+                value = candidate;
+                error = e;
+                steps += 1;
+                continue 'outer;
+            }
+        }
+        return (value, error, steps);
+    }
+}
+
+/// Checks that `property` holds for randomly generated values.
+/// Panics with a shrunk counterexample if it does not.
+pub fn check<G: Generator>(generator: &G, property: impl Fn(&G::Value) -> Result<(), String>) {
+    let seed = get_seed();
+    let cases: usize = env_or("AOC_TEST_CASES", 100);
+    let mut rng = Rng::new(seed);
+
+    for case in 0..cases {
+        let value = generator.generate(&mut rng);
+
+        if let Err(error) = property(&value) {
+            let (value, error, steps) = minimize(generator, value, error, &property);
+            panic!(
+                "Property failed after {} cases (AOC_TEST_SEED={seed}), shrunk {steps} times.\n\
+                Counterexample: {value:#?}\n{error}",
+                case + 1
+            );
+        }
+    }
+}
+
+/// Checks that two implementations agree on randomly generated values.
+/// Typically `reference` is a slow but obviously correct brute force and `subject` the optimized solver.
+pub fn differential<G: Generator, R: PartialEq + Debug>(
+    generator: &G,
+    reference: impl Fn(&G::Value) -> R,
+    subject: impl Fn(&G::Value) -> R,
+) {
+    check(generator, |value| {
+        let expected = reference(value);
+        let actual = subject(value);
+        if expected == actual {
+            Ok(())
+        } else {
+            Err(format!("Expected: {expected:?}\nActual: {actual:?}"))
+        }
+    });
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Numbers;
+
+    impl Generator for Numbers {
+        type Value = Vec<i64>;
+
+        fn generate(&self, rng: &mut Rng) -> Vec<i64> {
+            let len = rng.index(20);
+            (0..len).map(|_| rng.range(-100, 100)).collect()
+        }
+
+        fn shrink(&self, value: &Vec<i64>) -> Vec<Vec<i64>> {
+            let mut candidates = shrink_vec(value);
+            for (i, x) in value.iter().enumerate() {
+                for smaller in shrink_int(*x, 0) {
+                    let mut candidate = value.clone();
+                    candidate[i] = smaller;
+                    candidates.push(candidate);
+                }
+            }
+            candidates
+        }
+    }
+
+    #[test]
+    fn test_rng_range() {
+        let mut rng = Rng::new(42);
+        for _ in 0..1000 {
+            let x = rng.range(-3, 3);
+            assert!((-3..=3).contains(&x));
+        }
+    }
+
+    #[test]
+    fn test_differential_passes() {
+        differential(
+            &Numbers,
+            |v| v.iter().sum::<i64>(),
+            |v| v.iter().rev().sum::<i64>(),
+        );
+    }
+
+    #[test]
+    fn test_shrinks_counterexample() {
+        let failing = |v: &Vec<i64>| {
+            if v.iter().any(|x| *x > 50) {
+                Err("too large".to_string())
+            } else {
+                Ok(())
+            }
+        };
+        let (value, _, _) = minimize(&Numbers, vec![3, 99, -7, 80], "".into(), &failing);
+        assert_eq!(value, vec![51]);
+    }
+}